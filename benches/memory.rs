@@ -0,0 +1,82 @@
+//! Heap usage per entry, measured with a counting global allocator.
+//!
+//! ```text
+//! cargo bench --bench memory
+//! ```
+//!
+//! Prints one row per map type: live bytes after inserting `N` `u64 -> u64`
+//! entries, divided by `N`.
+
+use crossbeam_skiplist::SkipMap;
+use skiplist_rs::SkipList;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+
+const N: u64 = 100_000;
+
+/// Forwards to the system allocator, keeping a count of live bytes
+struct Counting;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LIVE.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        LIVE.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Live bytes held by whatever `build` returns
+fn measure<T>(build: impl FnOnce() -> T) -> usize {
+    let before = LIVE.load(Ordering::Relaxed);
+    let map = build();
+    let bytes = LIVE.load(Ordering::Relaxed) - before;
+    drop(map);
+    bytes
+}
+
+fn skiplist(p: f64) -> SkipList<u64, u64> {
+    let mut map = SkipList::with_params(4, p);
+    map.seed(42);
+    for k in 0..N {
+        map.insert(k, k);
+    }
+    map
+}
+
+fn main() {
+    let rows = [
+        ("SkipList (p = 0.25)", measure(|| skiplist(0.25))),
+        ("SkipList (p = 0.5)", measure(|| skiplist(0.5))),
+        ("SkipList (p = 0.75)", measure(|| skiplist(0.75))),
+        ("BTreeMap", measure(|| (0..N).map(|k| (k, k)).collect::<BTreeMap<_, _>>())),
+        ("HashMap", measure(|| (0..N).map(|k| (k, k)).collect::<HashMap<_, _>>())),
+        (
+            "Arc<RwLock<BTreeMap>>",
+            measure(|| Arc::new(RwLock::new((0..N).map(|k| (k, k)).collect::<BTreeMap<_, _>>()))),
+        ),
+        ("crossbeam SkipMap", measure(|| (0..N).map(|k| (k, k)).collect::<SkipMap<_, _>>())),
+    ];
+
+    println!("{} u64 -> u64 entries", N);
+    println!("{:<24} {:>12} {:>12}", "map", "bytes", "bytes/entry");
+    for (name, bytes) in rows {
+        println!("{:<24} {:>12} {:>12.1}", name, bytes, bytes as f64 / N as f64);
+    }
+}
@@ -0,0 +1,315 @@
+//! Throughput benchmarks against `BTreeMap`, `HashMap` and crossbeam's
+//! lock-free `SkipMap`.
+//!
+//! ```text
+//! cargo bench --bench skiplist
+//! cargo bench --bench skiplist -- mixed   # only the multi-threaded groups
+//! ```
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use crossbeam_skiplist::SkipMap;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use skiplist_rs::SkipList;
+use std::collections::{BTreeMap, HashMap};
+use std::hint::black_box;
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Entries in every prebuilt map
+const N: u64 = 10_000;
+/// Entries visited by each range scan
+const SCAN: u64 = 100;
+/// Operations each thread runs in the mixed workloads
+const OPS_PER_THREAD: u64 = 10_000;
+/// Percentage of mixed operations that are reads
+const READ_PERCENT: u32 = 90;
+
+/// The keys `0..N`, shuffled with a fixed seed so every run sees the same order
+fn shuffled_keys() -> Vec<u64> {
+    let mut keys: Vec<u64> = (0..N).collect();
+    keys.shuffle(&mut StdRng::seed_from_u64(42));
+    keys
+}
+
+/// Maps holding the even keys below `2 * N`, so odd keys are guaranteed misses
+fn even_skiplist() -> SkipList<u64, u64> {
+    let mut map = SkipList::new();
+    for k in 0..N {
+        map.insert(k * 2, k);
+    }
+    map
+}
+
+fn even_btree() -> BTreeMap<u64, u64> {
+    (0..N).map(|k| (k * 2, k)).collect()
+}
+
+fn even_hash() -> HashMap<u64, u64> {
+    (0..N).map(|k| (k * 2, k)).collect()
+}
+
+fn even_skipmap() -> SkipMap<u64, u64> {
+    (0..N).map(|k| (k * 2, k)).collect()
+}
+
+fn bench_insert(c: &mut Criterion) {
+    let orders = [("sequential", (0..N).collect::<Vec<_>>()), ("random", shuffled_keys())];
+
+    for (order, keys) in &orders {
+        let mut group = c.benchmark_group(format!("insert/{}", order));
+        group.throughput(Throughput::Elements(N));
+
+        group.bench_function("SkipList", |b| {
+            b.iter_with_large_drop(|| {
+                let mut map = SkipList::new();
+                for &k in keys {
+                    map.insert(k, k);
+                }
+                map
+            })
+        });
+        group.bench_function("BTreeMap", |b| {
+            b.iter_with_large_drop(|| {
+                let mut map = BTreeMap::new();
+                for &k in keys {
+                    map.insert(k, k);
+                }
+                map
+            })
+        });
+        group.bench_function("HashMap", |b| {
+            b.iter_with_large_drop(|| {
+                let mut map = HashMap::new();
+                for &k in keys {
+                    map.insert(k, k);
+                }
+                map
+            })
+        });
+        group.bench_function("crossbeam SkipMap", |b| {
+            b.iter_with_large_drop(|| {
+                let map = SkipMap::new();
+                for &k in keys {
+                    map.insert(k, k);
+                }
+                map
+            })
+        });
+        group.finish();
+    }
+}
+
+fn bench_get(c: &mut Criterion) {
+    let skiplist = even_skiplist();
+    let btree = even_btree();
+    let hash = even_hash();
+    let skipmap = even_skipmap();
+
+    // Even probes hit, odd probes miss
+    for (outcome, offset) in [("hit", 0), ("miss", 1)] {
+        let probes: Vec<u64> = shuffled_keys().into_iter().map(|k| k * 2 + offset).collect();
+        let mut group = c.benchmark_group(format!("get/{}", outcome));
+        group.throughput(Throughput::Elements(N));
+
+        group.bench_function("SkipList", |b| {
+            b.iter(|| probes.iter().filter_map(|k| skiplist.get(k)).count())
+        });
+        group.bench_function("BTreeMap", |b| {
+            b.iter(|| probes.iter().filter_map(|k| btree.get(k).copied()).count())
+        });
+        group.bench_function("HashMap", |b| {
+            b.iter(|| probes.iter().filter_map(|k| hash.get(k).copied()).count())
+        });
+        group.bench_function("crossbeam SkipMap", |b| {
+            b.iter(|| probes.iter().filter_map(|k| skipmap.get(k).map(|e| *e.value())).count())
+        });
+        group.finish();
+    }
+}
+
+fn bench_range(c: &mut Criterion) {
+    let skiplist = even_skiplist();
+    let btree = even_btree();
+    let skipmap = even_skipmap();
+    let mut rng = StdRng::seed_from_u64(7);
+    let starts: Vec<u64> = (0..100).map(|_| rng.gen_range(0..2 * (N - SCAN))).collect();
+
+    let mut group = c.benchmark_group("range");
+    group.throughput(Throughput::Elements(starts.len() as u64 * SCAN));
+
+    group.bench_function("SkipList", |b| {
+        b.iter(|| {
+            starts
+                .iter()
+                .map(|&s| skiplist.range(s..).take(SCAN as usize).map(|(_, v)| v).sum::<u64>())
+                .sum::<u64>()
+        })
+    });
+    group.bench_function("BTreeMap", |b| {
+        b.iter(|| {
+            starts
+                .iter()
+                .map(|&s| btree.range(s..).take(SCAN as usize).map(|(_, v)| *v).sum::<u64>())
+                .sum::<u64>()
+        })
+    });
+    group.bench_function("crossbeam SkipMap", |b| {
+        b.iter(|| {
+            starts
+                .iter()
+                .map(|&s| skipmap.range(s..).take(SCAN as usize).map(|e| *e.value()).sum::<u64>())
+                .sum::<u64>()
+        })
+    });
+    group.finish();
+}
+
+fn bench_p(c: &mut Criterion) {
+    let keys = shuffled_keys();
+    let mut group = c.benchmark_group("p");
+    group.throughput(Throughput::Elements(N));
+
+    for p in [0.25, 0.5, 0.75] {
+        group.bench_with_input(BenchmarkId::new("insert", p), &p, |b, &p| {
+            b.iter_with_large_drop(|| {
+                let mut map = SkipList::with_params(4, p);
+                for &k in &keys {
+                    map.insert(k, k);
+                }
+                map
+            })
+        });
+
+        let mut map = SkipList::with_params(4, p);
+        for &k in &keys {
+            map.insert(k, k);
+        }
+        group.bench_with_input(BenchmarkId::new("get", p), &p, |b, _| {
+            b.iter(|| keys.iter().filter_map(|k| map.get(k)).count())
+        });
+    }
+    group.finish();
+}
+
+/// Run `op` on `threads` threads at once, `OPS_PER_THREAD` times each, and
+/// return the wall time. Each thread gets its own seeded RNG.
+fn run_threads<F>(threads: usize, op: F) -> Duration
+where
+    F: Fn(&mut StdRng) + Sync,
+{
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for t in 0..threads {
+            let op = &op;
+            scope.spawn(move || {
+                let mut rng = StdRng::seed_from_u64(t as u64);
+                for _ in 0..OPS_PER_THREAD {
+                    op(&mut rng);
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn bench_mixed(c: &mut Criterion) {
+    for threads in [1, 4, 16] {
+        let mut group = c.benchmark_group(format!("mixed/{}-threads", threads));
+        group.throughput(Throughput::Elements(threads as u64 * OPS_PER_THREAD));
+        group.sample_size(20);
+
+        let skiplist = Arc::new(RwLock::new(even_skiplist()));
+        group.bench_function("RwLock<SkipList>", |b| {
+            b.iter_custom(|iters| {
+                (0..iters)
+                    .map(|_| {
+                        run_threads(threads, |rng| {
+                            let k = rng.gen_range(0..2 * N);
+                            if rng.gen_ratio(READ_PERCENT, 100) {
+                                black_box(skiplist.read().unwrap().get(&k));
+                            } else {
+                                skiplist.write().unwrap().insert(k, k);
+                            }
+                        })
+                    })
+                    .sum()
+            })
+        });
+
+        let btree = Arc::new(RwLock::new(even_btree()));
+        group.bench_function("RwLock<BTreeMap>", |b| {
+            b.iter_custom(|iters| {
+                (0..iters)
+                    .map(|_| {
+                        run_threads(threads, |rng| {
+                            let k = rng.gen_range(0..2 * N);
+                            if rng.gen_ratio(READ_PERCENT, 100) {
+                                black_box(btree.read().unwrap().get(&k).copied());
+                            } else {
+                                btree.write().unwrap().insert(k, k);
+                            }
+                        })
+                    })
+                    .sum()
+            })
+        });
+
+        let skipmap = even_skipmap();
+        group.bench_function("crossbeam SkipMap", |b| {
+            b.iter_custom(|iters| {
+                (0..iters)
+                    .map(|_| {
+                        run_threads(threads, |rng| {
+                            let k = rng.gen_range(0..2 * N);
+                            if rng.gen_ratio(READ_PERCENT, 100) {
+                                black_box(skipmap.get(&k).map(|e| *e.value()));
+                            } else {
+                                skipmap.insert(k, k);
+                            }
+                        })
+                    })
+                    .sum()
+            })
+        });
+        group.finish();
+    }
+}
+
+/// Batched lookups against one `get` per key, for growing batch sizes
+fn bench_batch(c: &mut Criterion) {
+    let skiplist = even_skiplist();
+    let keys = shuffled_keys();
+
+    for size in [100, 1_000, N as usize] {
+        let probes: Vec<u64> = keys[..size].iter().map(|k| k * 2).collect();
+        let mut group = c.benchmark_group(format!("batch/{}", size));
+        group.throughput(Throughput::Elements(size as u64));
+
+        group.bench_function("get", |b| b.iter(|| probes.iter().filter_map(|k| skiplist.get(k)).count()));
+        group.bench_function("get_many", |b| b.iter(|| skiplist.get_many(black_box(&probes))));
+        group.finish();
+    }
+}
+
+/// Lookups that each land a few keys after the last, with and without a finger
+fn bench_finger(c: &mut Criterion) {
+    let skiplist = even_skiplist();
+    let probes: Vec<u64> = (0..N).map(|k| k * 2).collect();
+    let mut group = c.benchmark_group("finger/sequential");
+    group.throughput(Throughput::Elements(N));
+
+    group.bench_function("get", |b| b.iter(|| probes.iter().filter_map(|k| skiplist.get(k)).count()));
+    group.bench_function("get_near", |b| {
+        b.iter(|| {
+            let mut finger = skiplist.finger();
+            probes.iter().filter_map(|k| skiplist.get_near(&mut finger, k)).count()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_insert, bench_get, bench_range, bench_p, bench_mixed, bench_batch, bench_finger);
+criterion_main!(benches);
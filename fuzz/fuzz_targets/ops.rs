@@ -0,0 +1,77 @@
+//! Interprets the input as a script of list operations, mirrors every one on a
+//! `BTreeMap` and checks the list's invariants after each step.
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use skiplist_rs::SkipList;
+use std::collections::BTreeMap;
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    Insert(u8, u16),
+    Get(u8),
+    Remove(u8),
+    Range(u8, u8),
+    RetainBelow(u8),
+    DrainRange(u8, u8),
+    SplitOffAppend(u8),
+    Snapshot,
+}
+
+#[derive(Arbitrary, Debug)]
+struct Script {
+    initial_max: u8,
+    seed: u64,
+    ops: Vec<Op>,
+}
+
+fuzz_target!(|script: Script| {
+    let mut skiplist = SkipList::with_params(script.initial_max as usize % 8 + 1, 0.5);
+    skiplist.seed(script.seed);
+    let mut model = BTreeMap::new();
+
+    for op in script.ops {
+        match op {
+            Op::Insert(k, v) => assert_eq!(skiplist.insert(k, v), model.insert(k, v)),
+            Op::Get(k) => assert_eq!(skiplist.get(&k), model.get(&k).copied()),
+            Op::Remove(k) => assert_eq!(skiplist.remove(&k), model.remove(&k)),
+            Op::Range(a, b) => {
+                let (a, b) = (a.min(b), a.max(b));
+                let expected: Vec<_> = model.range(a..b).map(|(k, v)| (*k, *v)).collect();
+                assert_eq!(skiplist.range(a..b).collect::<Vec<_>>(), expected);
+                let reversed: Vec<_> = expected.into_iter().rev().collect();
+                assert_eq!(skiplist.range(a..b).rev().collect::<Vec<_>>(), reversed);
+            }
+            Op::RetainBelow(k) => {
+                skiplist.retain(|key, _| *key < k);
+                model.retain(|key, _| *key < k);
+            }
+            Op::DrainRange(a, b) => {
+                let (a, b) = (a.min(b), a.max(b));
+                let drained: Vec<_> = skiplist.drain_range(a..=b).collect();
+                let keys: Vec<_> = model.range(a..=b).map(|(k, _)| *k).collect();
+                let expected: Vec<_> = keys.iter().map(|k| (*k, model.remove(k).unwrap())).collect();
+                assert_eq!(drained, expected);
+            }
+            Op::SplitOffAppend(k) => {
+                let mut right = skiplist.split_off(&k);
+                assert_eq!(skiplist.check_invariants(), Ok(()));
+                assert_eq!(right.check_invariants(), Ok(()));
+                assert_eq!(right.len(), model.range(k..).count());
+                skiplist.append(&mut right);
+            }
+            Op::Snapshot => {
+                let bytes = skiplist.to_snapshot();
+                let copy = SkipList::<u8, u16>::from_snapshot(&bytes).expect("snapshot round trip");
+                assert!(copy.iter().eq(skiplist.iter()));
+                skiplist = copy;
+                skiplist.seed(script.seed);
+            }
+        }
+        assert_eq!(skiplist.len(), model.len());
+        assert_eq!(skiplist.check_invariants(), Ok(()));
+    }
+
+    assert!(skiplist.iter().eq(model.into_iter()));
+});
@@ -0,0 +1,30 @@
+//! Feeds arbitrary bytes to the snapshot loader, which must return an error
+//! rather than panic, and checks that whatever it accepts is a valid list that
+//! survives another round trip. The streaming loader must agree with the
+//! in-memory one on every input.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use skiplist_rs::{SkipList, SnapshotValue};
+
+fn check<K, V>(data: &[u8])
+where
+    K: Ord + Clone + SnapshotValue,
+    V: Clone + PartialEq + SnapshotValue,
+{
+    let streamed = SkipList::<K, V>::read_snapshot(data);
+    let Ok(skiplist) = SkipList::<K, V>::from_snapshot(data) else {
+        assert!(streamed.is_err());
+        return;
+    };
+    assert!(streamed.expect("streaming loader accepts it too").iter().eq(skiplist.iter()));
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+
+    let copy = SkipList::<K, V>::from_snapshot(&skiplist.to_snapshot()).expect("re-encoded snapshot loads");
+    assert!(copy.iter().eq(skiplist.iter()));
+}
+
+fuzz_target!(|data: &[u8]| {
+    check::<u16, String>(data);
+    check::<Vec<u8>, u64>(data);
+});
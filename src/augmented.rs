@@ -0,0 +1,460 @@
+//! Skip list with range aggregates.
+//!
+//! An [`AugmentedSkipList`] stores, next to every forward link, the aggregate
+//! of the entries that link skips over, the same way an indexable skip list
+//! stores link widths. A link from `a` to `b` at level `n` holds the
+//! aggregate of every entry after `a` up to and including `b`:
+//!
+//! ```text
+//! Level 2: [H] -------------(19)------------> [9] -----(12)-----> end
+//! Level 1: [H] --(4)--> [4] -------(15)-----> [9] -----(12)-----> end
+//! Level 0: [H] --(4)--> [4] --(6)--> [6] --(9)--> [9] --(12)--> [12] --> end
+//!
+//! (Sum aggregates, each entry's value equal to its key)
+//! ```
+//!
+//! [`aggregate`](AugmentedSkipList::aggregate) walks from the entry before
+//! the range start up to the range end using the tallest links that stay
+//! inside the range, so it combines O(log n) stored aggregates instead of
+//! visiting every entry. Each insert, update or remove recomputes the
+//! aggregates of the links on its search path, each from the level below.
+//!
+//! Every write touches a link on every level, so nodes live in an arena and
+//! link by index instead of sitting behind per-node locks like
+//! [`SkipList`](crate::SkipList)'s.
+
+use crate::level;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::marker::PhantomData;
+use std::ops::{Add, Bound, RangeBounds};
+
+/// An associative way of combining values, with an identity.
+///
+/// `combine` must be associative, and `empty` must leave any aggregate
+/// unchanged when combined with it on either side. It need not be
+/// commutative: aggregates are always combined in key order.
+pub trait Monoid<V> {
+    /// The aggregate type
+    type Agg: Clone;
+
+    /// Aggregate of no entries.
+    fn empty() -> Self::Agg;
+
+    /// Aggregate of a single value.
+    fn lift(value: &V) -> Self::Agg;
+
+    /// Aggregate of `left` followed by `right`.
+    fn combine(left: &Self::Agg, right: &Self::Agg) -> Self::Agg;
+}
+
+/// Sum of the values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sum;
+
+impl<V> Monoid<V> for Sum
+where
+    V: Clone + Default + Add<Output = V>,
+{
+    type Agg = V;
+
+    fn empty() -> V {
+        V::default()
+    }
+
+    fn lift(value: &V) -> V {
+        value.clone()
+    }
+
+    fn combine(left: &V, right: &V) -> V {
+        left.clone() + right.clone()
+    }
+}
+
+/// Number of entries.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Count;
+
+impl<V> Monoid<V> for Count {
+    type Agg = usize;
+
+    fn empty() -> usize {
+        0
+    }
+
+    fn lift(_: &V) -> usize {
+        1
+    }
+
+    fn combine(left: &usize, right: &usize) -> usize {
+        left + right
+    }
+}
+
+/// Smallest value, or `None` for no entries.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Min;
+
+impl<V: Ord + Clone> Monoid<V> for Min {
+    type Agg = Option<V>;
+
+    fn empty() -> Option<V> {
+        None
+    }
+
+    fn lift(value: &V) -> Option<V> {
+        Some(value.clone())
+    }
+
+    fn combine(left: &Option<V>, right: &Option<V>) -> Option<V> {
+        match (left, right) {
+            (Some(l), Some(r)) => Some(l.min(r).clone()),
+            (l, r) => l.clone().or_else(|| r.clone()),
+        }
+    }
+}
+
+/// Largest value, or `None` for no entries.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Max;
+
+impl<V: Ord + Clone> Monoid<V> for Max {
+    type Agg = Option<V>;
+
+    fn empty() -> Option<V> {
+        None
+    }
+
+    fn lift(value: &V) -> Option<V> {
+        Some(value.clone())
+    }
+
+    fn combine(left: &Option<V>, right: &Option<V>) -> Option<V> {
+        match (left, right) {
+            (Some(l), Some(r)) => Some(l.max(r).clone()),
+            (l, r) => l.clone().or_else(|| r.clone()),
+        }
+    }
+}
+
+/// Two aggregates at once, such as `(Sum, Count)` for a mean.
+impl<V, A, B> Monoid<V> for (A, B)
+where
+    A: Monoid<V>,
+    B: Monoid<V>,
+{
+    type Agg = (A::Agg, B::Agg);
+
+    fn empty() -> Self::Agg {
+        (A::empty(), B::empty())
+    }
+
+    fn lift(value: &V) -> Self::Agg {
+        (A::lift(value), B::lift(value))
+    }
+
+    fn combine(left: &Self::Agg, right: &Self::Agg) -> Self::Agg {
+        (A::combine(&left.0, &right.0), B::combine(&left.1, &right.1))
+    }
+}
+
+/// Index of the head in the arena
+const HEAD: usize = 0;
+
+/// A forward link and the aggregate of the entries it skips over
+struct Link<A> {
+    next: Option<usize>,
+    agg: A,
+}
+
+struct Node<K, V, A> {
+    /// `None` for the head and for free slots
+    entry: Option<(K, V)>,
+    links: Vec<Link<A>>,
+}
+
+/// A sorted map that answers aggregate queries over key ranges in
+/// O(log n).
+///
+/// `M` picks the aggregate: [`Sum`], [`Count`], [`Min`], [`Max`], a pair of
+/// them, or any other [`Monoid`].
+///
+/// # Examples
+///
+/// ```
+/// use skiplist_rs::{AugmentedSkipList, Sum};
+///
+/// let mut sales: AugmentedSkipList<u32, u64, Sum> = AugmentedSkipList::new();
+/// sales.insert(1, 100);
+/// sales.insert(2, 250);
+/// sales.insert(3, 75);
+///
+/// assert_eq!(sales.aggregate(2..=3), 325);
+/// assert_eq!(sales.aggregate(..), 425);
+/// ```
+pub struct AugmentedSkipList<K, V, M: Monoid<V>> {
+    nodes: Vec<Node<K, V, M::Agg>>,
+    /// Arena slots freed by `remove`
+    free: Vec<usize>,
+    max: usize,
+    len: usize,
+    p: f64,
+    /// Seeded tower heights, once [`seed`](Self::seed) is called
+    rng: Option<StdRng>,
+    _monoid: PhantomData<M>,
+}
+
+impl<K, V, M> Default for AugmentedSkipList<K, V, M>
+where
+    K: Ord,
+    M: Monoid<V>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, M> AugmentedSkipList<K, V, M>
+where
+    K: Ord,
+    M: Monoid<V>,
+{
+    /// Creates an empty list with probability 0.5 and 4 initial levels.
+    pub fn new() -> Self {
+        Self::with_params(4, 0.5)
+    }
+
+    /// Creates an empty list with `initial_max` levels and tower probability
+    /// `p`, as [`SkipList::with_params`](crate::SkipList::with_params) does.
+    pub fn with_params(initial_max: usize, p: f64) -> Self {
+        let max = initial_max.max(1);
+        let head = Node { entry: None, links: (0..max).map(|_| Link { next: None, agg: M::empty() }).collect() };
+        AugmentedSkipList {
+            nodes: vec![head],
+            free: Vec::new(),
+            max,
+            len: 0,
+            p,
+            rng: None,
+            _monoid: PhantomData,
+        }
+    }
+
+    /// Seeds the tower heights, which are drawn the same way as a
+    /// [`SkipList`](crate::SkipList)'s.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = Some(StdRng::seed_from_u64(seed));
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the list has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn key(&self, node: usize) -> &K {
+        &self.nodes[node].entry.as_ref().expect("linked node has an entry").0
+    }
+
+    fn next(&self, node: usize, level: usize) -> Option<usize> {
+        self.nodes[node].links[level].next
+    }
+
+    /// Rightmost node at every level whose key passes `before`
+    fn predecessors(&self, before: impl Fn(&K) -> bool) -> Vec<usize> {
+        let mut update = vec![HEAD; self.max];
+        let mut curr = HEAD;
+        for level in (0..self.max).rev() {
+            while let Some(next) = self.next(curr, level).filter(|&next| before(self.key(next))) {
+                curr = next;
+            }
+            update[level] = curr;
+        }
+        update
+    }
+
+    /// Recompute the aggregate of `node`'s link at `level` from the level below
+    fn refresh(&mut self, node: usize, level: usize) {
+        let end = self.next(node, level);
+        let agg = if level == 0 {
+            match end {
+                Some(next) => M::lift(&self.nodes[next].entry.as_ref().expect("linked node has an entry").1),
+                None => M::empty(),
+            }
+        } else {
+            let mut agg = M::empty();
+            let mut curr = node;
+            loop {
+                let link = &self.nodes[curr].links[level - 1];
+                agg = M::combine(&agg, &link.agg);
+                match link.next {
+                    next if next == end => break,
+                    Some(next) => curr = next,
+                    None => break,
+                }
+            }
+            agg
+        };
+        self.nodes[node].links[level].agg = agg;
+    }
+
+    /// Add head levels so the list can keep towers about log2(len) tall
+    fn resize(&mut self) {
+        let optimal = level::optimal_levels(self.len);
+        while self.max < optimal {
+            self.nodes[HEAD].links.push(Link { next: None, agg: M::empty() });
+            self.refresh(HEAD, self.max);
+            self.max += 1;
+        }
+    }
+
+    /// Inserts a key-value pair, returning the old value if the key was
+    /// present.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let update = self.predecessors(|k| k < &key);
+
+        if let Some(next) = self.next(update[0], 0).filter(|&next| self.key(next) == &key) {
+            let entry = self.nodes[next].entry.as_mut().expect("linked node has an entry");
+            let old = std::mem::replace(&mut entry.1, value);
+            for (level, &pred) in update.iter().enumerate() {
+                self.refresh(pred, level);
+            }
+            return Some(old);
+        }
+
+        let height = level::level_from(&mut self.rng, self.p, self.max);
+        let links = (0..height).map(|level| Link { next: self.next(update[level], level), agg: M::empty() }).collect();
+        let node = Node { entry: Some((key, value)), links };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+
+        for (level, &pred) in update.iter().enumerate() {
+            if level < height {
+                self.nodes[pred].links[level].next = Some(index);
+                self.refresh(index, level);
+            }
+            self.refresh(pred, level);
+        }
+        self.len += 1;
+        self.resize();
+        None
+    }
+
+    /// Removes a key, returning its value if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let update = self.predecessors(|k| k < key);
+        let index = self.next(update[0], 0).filter(|&next| self.key(next) == key)?;
+
+        let links = std::mem::take(&mut self.nodes[index].links);
+        for (level, link) in links.iter().enumerate() {
+            self.nodes[update[level]].links[level].next = link.next;
+        }
+        for (level, &pred) in update.iter().enumerate() {
+            self.refresh(pred, level);
+        }
+
+        self.len -= 1;
+        self.free.push(index);
+        self.nodes[index].entry.take().map(|(_, value)| value)
+    }
+
+    /// Returns a reference to the value for `key`.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let pred = self.predecessors(|k| k < key)[0];
+        let next = self.next(pred, 0).filter(|&next| self.key(next) == key)?;
+        self.nodes[next].entry.as_ref().map(|(_, value)| value)
+    }
+
+    /// Returns the aggregate of the values whose keys fall in `range`, in
+    /// O(log n) expected time.
+    ///
+    /// An empty range gives [`Monoid::empty`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::{AugmentedSkipList, Count, Max, Sum};
+    ///
+    /// let mut temps: AugmentedSkipList<u32, i32, (Max, (Sum, Count))> = AugmentedSkipList::new();
+    /// for (hour, temp) in [(6, 12), (9, 17), (12, 23), (15, 21), (18, 16)] {
+    ///     temps.insert(hour, temp);
+    /// }
+    ///
+    /// let (max, (sum, count)) = temps.aggregate(9..16);
+    /// assert_eq!(max, Some(23));
+    /// assert_eq!(sum / count as i32, 20);
+    /// ```
+    pub fn aggregate<R>(&self, range: R) -> M::Agg
+    where
+        R: RangeBounds<K>,
+    {
+        let mut curr = match range.start_bound() {
+            Bound::Unbounded => HEAD,
+            Bound::Included(start) => self.predecessors(|k| k < start)[0],
+            Bound::Excluded(start) => self.predecessors(|k| k <= start)[0],
+        };
+        let within = |k: &K| match range.end_bound() {
+            Bound::Unbounded => true,
+            Bound::Included(end) => k <= end,
+            Bound::Excluded(end) => k < end,
+        };
+
+        // Once a link at some level overshoots the range, every entry left in
+        // the range is shorter than that level, so `cap` only ever shrinks
+        let mut agg = M::empty();
+        let mut cap = self.max;
+        'walk: loop {
+            let mut level = self.nodes[curr].links.len().min(cap);
+            while level > 0 {
+                level -= 1;
+                let link = &self.nodes[curr].links[level];
+                match link.next {
+                    Some(next) if within(self.key(next)) => {
+                        agg = M::combine(&agg, &link.agg);
+                        curr = next;
+                        continue 'walk;
+                    }
+                    _ => cap = level,
+                }
+            }
+            return agg;
+        }
+    }
+
+    /// Returns an iterator over the entries in key order.
+    pub fn iter(&self) -> Iter<'_, K, V, M> {
+        Iter { list: self, next: self.next(HEAD, 0) }
+    }
+}
+
+/// Iterator over the entries of an [`AugmentedSkipList`] in key order.
+pub struct Iter<'a, K, V, M: Monoid<V>> {
+    list: &'a AugmentedSkipList<K, V, M>,
+    next: Option<usize>,
+}
+
+impl<'a, K, V, M> Iterator for Iter<'a, K, V, M>
+where
+    K: Ord,
+    M: Monoid<V>,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = &self.list.nodes[self.next?];
+        self.next = node.links[0].next;
+        node.entry.as_ref().map(|(key, value)| (key, value))
+    }
+}
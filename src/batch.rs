@@ -0,0 +1,151 @@
+//! Batch operations: `insert_batch`, `get_many` and `remove_batch`.
+//!
+//! A batch is handled in key order, and each key's search starts from the
+//! predecessors found for the key before it instead of from the head. The
+//! search climbs from that *finger* only as high as it has to, then walks
+//! down as usual:
+//!
+//! ```text
+//! Level 2: [H] ---------------------------> [40] ----------> end
+//! Level 1: [H] ----------> [20] ----------> [40] --> [50] -> end
+//! Level 0: [H] --> [10] -> [20] --> [30] -> [40] --> [50] -> end
+//!
+//! After 25 the finger is [20, 20, H]. Finding 35 only walks level 0 from
+//! 20; finding 45 instead climbs to level 2, where the head's link passes it.
+//! ```
+//!
+//! Reaching a key d positions past the previous one costs O(log d) expected,
+//! so a sorted batch of k keys spread over n entries costs about
+//! O(k log(n/k)) rather than O(k log n). Results still come back in the
+//! order the keys were given.
+
+use crate::SkipList;
+use std::sync::Arc;
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord,
+{
+    /// Positions of `keys` in ascending key order; equal keys keep their
+    /// input order
+    fn sorted_order<T>(keys: &[T], key: impl Fn(&T) -> &K) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..keys.len()).collect();
+        order.sort_by(|&a, &b| key(&keys[a]).cmp(key(&keys[b])));
+        order
+    }
+
+    /// Inserts every entry, returning what each [`insert`](Self::insert)
+    /// would have returned, in input order.
+    ///
+    /// The entries are applied in key order, with each search starting from
+    /// the previous key's position, so a batch of k entries costs about
+    /// O(k log(n/k)) searching instead of O(k log n). Repeated keys are
+    /// applied in input order, so the last one wins. A capacity limit is
+    /// enforced once, after the whole batch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.insert(2, "old");
+    ///
+    /// let replaced = skiplist.insert_batch(vec![(3, "c"), (2, "b"), (1, "a"), (3, "C")]);
+    /// assert_eq!(replaced, vec![None, Some("old"), None, Some("c")]);
+    /// assert_eq!(skiplist.get(&3), Some("C"));
+    /// ```
+    pub fn insert_batch<I>(&mut self, entries: I) -> Vec<Option<V>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut entries: Vec<Option<(K, V)>> = entries.into_iter().map(Some).collect();
+        let order = Self::sorted_order(&entries, |entry| &entry.as_ref().expect("not taken yet").0);
+        let mut results: Vec<Option<V>> = entries.iter().map(|_| None).collect();
+
+        let mut finger = vec![Arc::clone(&self.head); self.max];
+        for index in order {
+            let (key, value) = entries[index].take().expect("each entry is taken once");
+            self.finger_to(&mut finger, &key);
+            // The finger stays behind the key, so a repeat of it is found
+            results[index] = self.insert_after(&finger, key, value);
+        }
+
+        self.evict_to_capacity();
+        results
+    }
+
+    /// Looks up every key, returning the values in input order.
+    ///
+    /// Lookups run in key order, each starting from the previous key's
+    /// position, so k keys cost about O(k log(n/k)) instead of O(k log n).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// for i in 0..100 {
+    ///     skiplist.insert(i, i * 10);
+    /// }
+    ///
+    /// assert_eq!(skiplist.get_many(&[42, 7, 500, 7]), vec![Some(420), Some(70), None, Some(70)]);
+    /// ```
+    pub fn get_many(&self, keys: &[K]) -> Vec<Option<V>>
+    where
+        V: Clone,
+    {
+        let mut results = vec![None; keys.len()];
+        let mut finger = vec![Arc::clone(&self.head); self.max];
+        for index in Self::sorted_order(keys, |key| key) {
+            let key = &keys[index];
+            self.finger_to(&mut finger, key);
+            let next = self.read_node(&finger[0]).fwd[0].clone();
+            let found = next.filter(|next| self.read_node(next).key.as_ref() == Some(key));
+            let Some(node) = found else {
+                record!(self, misses);
+                continue;
+            };
+            record!(self, hits);
+            let node_ref = self.read_node(&node);
+            self.bounds.touch(&node_ref);
+            results[index] = node_ref.val.clone();
+        }
+        results
+    }
+
+    /// Removes every key, returning the removed values in input order.
+    ///
+    /// Removals run in key order, each starting from the previous key's
+    /// position, so k keys cost about O(k log(n/k)) instead of O(k log n).
+    /// A key given twice is removed once; the second gets `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// for i in 0..10 {
+    ///     skiplist.insert(i, i * 10);
+    /// }
+    ///
+    /// assert_eq!(skiplist.remove_batch(&[8, 3, 3, 42]), vec![Some(80), Some(30), None, None]);
+    /// assert_eq!(skiplist.len(), 8);
+    /// ```
+    pub fn remove_batch(&mut self, keys: &[K]) -> Vec<Option<V>> {
+        let mut results: Vec<Option<V>> = keys.iter().map(|_| None).collect();
+        let mut finger = vec![Arc::clone(&self.head); self.max];
+        for index in Self::sorted_order(keys, |key| key) {
+            let key = &keys[index];
+            self.finger_to(&mut finger, key);
+            let next = self.read_node(&finger[0]).fwd[0].clone();
+            if let Some(node) = next.filter(|next| self.read_node(next).key.as_ref() == Some(key)) {
+                // The finger stays valid: it holds the node's predecessors
+                results[index] = self.unlink(&finger, &node).map(|(_, value)| value);
+            }
+        }
+        results
+    }
+}
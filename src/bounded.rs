@@ -0,0 +1,418 @@
+//! Capacity limits and eviction.
+//!
+//! A list given a [`Capacity`] with [`SkipList::set_capacity`] never holds
+//! more than that many entries (or bytes): every insert that goes over the
+//! limit evicts entries chosen by the [`EvictionPolicy`] until it fits again.
+//! Evicted entries are passed to the callback set with
+//! [`SkipList::on_evict`] and returned by [`SkipList::insert_evicting`].
+//!
+//! Byte usage is an estimate: the node allocation and its tower of links,
+//! plus whatever the weigher set with [`SkipList::set_weigher`] reports for
+//! the heap data a key and value own. Allocator overhead is not counted.
+//!
+//! Only [`insert`](SkipList::insert), [`insert_evicting`](SkipList::insert_evicting),
+//! [`append`](SkipList::append) and [`set_capacity`](SkipList::set_capacity)
+//! evict. Cursor inserts do not, so a cursor never has its entry pulled out
+//! from under it; the next insert brings the list back under the limit.
+//! The second half returned by [`split_off`](SkipList::split_off) starts out
+//! unbounded.
+
+use crate::sync::{Arc, RwLock, Weak};
+use crate::{Link, Node, NodeRef, SkipList};
+use std::collections::BTreeMap;
+use std::mem;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Upper bound on the size of a [`SkipList`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capacity {
+    /// At most this many entries
+    Entries(usize),
+    /// At most this many bytes, as estimated by [`SkipList::memory_usage`]
+    Bytes(usize),
+}
+
+/// Which entry a full [`SkipList`] gives up to make room.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EvictionPolicy {
+    /// The entry with the smallest key
+    #[default]
+    SmallestKey,
+    /// The entry with the largest key
+    LargestKey,
+    /// The entry least recently inserted, updated or read with `get`.
+    ///
+    /// Entries are indexed by when they were last used, so each eviction is
+    /// O(log n); in exchange every access also updates that index.
+    LeastRecentlyUsed,
+}
+
+/// What [`SkipList::insert_evicting`] did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inserted<K, V> {
+    /// The value the key had before, if any
+    pub replaced: Option<V>,
+    /// Entries evicted to make room, in eviction order
+    pub evicted: Vec<(K, V)>,
+}
+
+type Weigher<K, V> = Box<dyn Fn(&K, &V) -> usize + Send + Sync>;
+type EvictCallback<K, V> = Box<dyn FnMut(&K, &V) + Send + Sync>;
+type RecencyIndex<K, V> = BTreeMap<u64, Weak<RwLock<Node<K, V>>>>;
+
+/// Capacity settings and usage bookkeeping carried by every list
+pub(crate) struct Bounds<K, V> {
+    capacity: Option<Capacity>,
+    policy: EvictionPolicy,
+    /// Estimated bytes of every entry, kept up to date while a byte capacity is set
+    used: usize,
+    /// Source of access stamps for least-recently-used eviction
+    tick: AtomicU64,
+    /// Every node by its access stamp, kept while accesses are tracked.
+    /// Lookups stamp through `&self`, hence the lock; it is only ever taken
+    /// after a node lock, never before one.
+    recency: Mutex<RecencyIndex<K, V>>,
+    weigher: Option<Weigher<K, V>>,
+    on_evict: Option<EvictCallback<K, V>>,
+}
+
+impl<K, V> Default for Bounds<K, V> {
+    fn default() -> Self {
+        Bounds {
+            capacity: None,
+            policy: EvictionPolicy::SmallestKey,
+            used: 0,
+            tick: AtomicU64::new(0),
+            recency: Mutex::new(BTreeMap::new()),
+            weigher: None,
+            on_evict: None,
+        }
+    }
+}
+
+impl<K, V> Bounds<K, V> {
+    /// Whether byte usage is being tracked
+    fn counts_bytes(&self) -> bool {
+        matches!(self.capacity, Some(Capacity::Bytes(_)))
+    }
+
+    /// Whether accesses need stamping
+    fn tracks_access(&self) -> bool {
+        self.capacity.is_some() && self.policy == EvictionPolicy::LeastRecentlyUsed
+    }
+
+    /// Estimated bytes of an entry with a tower of `height` links
+    fn entry_bytes(&self, key: &K, val: &V, height: usize) -> usize {
+        // The Arc's two counts, the lock and node, and the tower's links
+        let node = 2 * mem::size_of::<usize>() + mem::size_of::<RwLock<Node<K, V>>>();
+        let heap = self.weigher.as_ref().map_or(0, |weigh| weigh(key, val));
+        node + height * mem::size_of::<Link<K, V>>() + heap
+    }
+
+    /// Next access stamp
+    fn stamp(&self) -> u64 {
+        self.tick.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Mark `node` as just used, if anything is watching
+    pub(crate) fn touch(&self, node: &Node<K, V>) {
+        if self.tracks_access() {
+            // Restamp under the index lock, or a concurrent touch of the
+            // same node could re-key the entry out from under this one
+            let mut recency = self.recency.lock().unwrap();
+            let stamp = self.stamp();
+            let old = node.accessed.swap(stamp, Ordering::Relaxed);
+            if let Some(weak) = recency.remove(&old) {
+                recency.insert(stamp, weak);
+            }
+        }
+    }
+
+    /// Account for a node that was just linked in
+    pub(crate) fn linked(&mut self, node: &NodeRef<K, V>) {
+        let node_ref = node.read().unwrap();
+        if self.counts_bytes() {
+            if let (Some(key), Some(val)) = (&node_ref.key, &node_ref.val) {
+                self.used += self.entry_bytes(key, val, node_ref.height());
+            }
+        }
+        if self.tracks_access() {
+            let stamp = self.stamp();
+            node_ref.accessed.store(stamp, Ordering::Relaxed);
+            self.recency.get_mut().unwrap().insert(stamp, Arc::downgrade(node));
+        }
+    }
+
+    /// Account for a node that is about to be unlinked
+    pub(crate) fn unlinked(&mut self, node: &Node<K, V>) {
+        if self.counts_bytes() {
+            if let (Some(key), Some(val)) = (&node.key, &node.val) {
+                self.used = self.used.saturating_sub(self.entry_bytes(key, val, node.height()));
+            }
+        }
+        if self.tracks_access() {
+            self.recency.get_mut().unwrap().remove(&node.accessed.load(Ordering::Relaxed));
+        }
+    }
+
+    /// Bytes `node` counts toward a byte capacity (0 when none is set)
+    pub(crate) fn weigh(&self, node: &Node<K, V>) -> usize {
+        match (&node.key, &node.val) {
+            (Some(key), Some(val)) if self.counts_bytes() => self.entry_bytes(key, val, node.height()),
+            _ => 0,
+        }
+    }
+
+    /// Account for `node`'s value having changed in place from `before` bytes
+    pub(crate) fn reweighed(&mut self, node: &Node<K, V>, before: usize) {
+        if self.counts_bytes() {
+            self.used = self.used.saturating_sub(before) + self.weigh(node);
+        }
+    }
+
+    /// Account for `node`'s value having just replaced `old`
+    pub(crate) fn updated(&mut self, node: &Node<K, V>, old: &V) {
+        if self.counts_bytes() {
+            if let (Some(key), Some(val)) = (&node.key, &node.val) {
+                self.used = self.used.saturating_sub(self.entry_bytes(key, old, node.height()));
+                self.used += self.entry_bytes(key, val, node.height());
+            }
+        }
+        self.touch(node);
+    }
+}
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord,
+{
+    /// Limits the list to `capacity`, evicting by `policy` whenever an insert
+    /// goes over it.
+    ///
+    /// If the list is already over the new limit, entries are evicted right
+    /// away and returned.
+    ///
+    /// [`insert`](Self::insert) keeps its `Option<V>` return so existing
+    /// callers are unaffected; the entries it evicts only reach the
+    /// [`on_evict`](Self::on_evict) callback. Use
+    /// [`insert_evicting`](Self::insert_evicting) to get them back alongside
+    /// the replaced value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::{Capacity, EvictionPolicy, SkipList};
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// for i in 0..5 {
+    ///     skiplist.insert(i, i * 10);
+    /// }
+    ///
+    /// let evicted = skiplist.set_capacity(Capacity::Entries(3), EvictionPolicy::LargestKey);
+    /// assert_eq!(evicted, vec![(4, 40), (3, 30)]);
+    ///
+    /// skiplist.insert(10, 100);
+    /// assert_eq!(skiplist.len(), 3);
+    /// assert_eq!(skiplist.get(&10), None);
+    /// ```
+    pub fn set_capacity(&mut self, capacity: Capacity, policy: EvictionPolicy) -> Vec<(K, V)> {
+        self.bounds.capacity = Some(capacity);
+        self.bounds.policy = policy;
+        self.recount_usage();
+        self.evict_to_capacity()
+    }
+
+    /// Removes the capacity limit.
+    pub fn clear_capacity(&mut self) {
+        self.bounds.capacity = None;
+        self.bounds.used = 0;
+        self.bounds.recency.get_mut().unwrap().clear();
+    }
+
+    /// Returns the capacity limit and eviction policy, if one is set.
+    pub fn capacity(&self) -> Option<(Capacity, EvictionPolicy)> {
+        self.bounds.capacity.map(|capacity| (capacity, self.bounds.policy))
+    }
+
+    /// Calls `callback` with every entry evicted to stay under the capacity.
+    ///
+    /// Replaces any callback set before.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::{Capacity, EvictionPolicy, SkipList};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let evicted = Arc::new(Mutex::new(Vec::new()));
+    /// let sink = Arc::clone(&evicted);
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.on_evict(move |k: &i32, _: &&str| sink.lock().unwrap().push(*k));
+    /// skiplist.set_capacity(Capacity::Entries(2), EvictionPolicy::SmallestKey);
+    ///
+    /// skiplist.insert(1, "one");
+    /// skiplist.insert(2, "two");
+    /// skiplist.insert(3, "three");
+    /// assert_eq!(*evicted.lock().unwrap(), vec![1]);
+    /// ```
+    pub fn on_evict<F>(&mut self, callback: F)
+    where
+        F: FnMut(&K, &V) + Send + Sync + 'static,
+    {
+        self.bounds.on_evict = Some(Box::new(callback));
+    }
+
+    /// Counts `weigher(key, value)` extra bytes per entry toward a byte
+    /// capacity, for heap data the entry owns (a `String`'s buffer, say).
+    ///
+    /// Without a weigher only the node itself is counted.
+    pub fn set_weigher<F>(&mut self, weigher: F)
+    where
+        F: Fn(&K, &V) -> usize + Send + Sync + 'static,
+    {
+        self.bounds.weigher = Some(Box::new(weigher));
+        self.recount_usage();
+    }
+
+    /// Returns the estimated bytes held by the entries.
+    ///
+    /// Kept up to date incrementally while a [`Capacity::Bytes`] limit is set;
+    /// otherwise computed by walking the list.
+    pub fn memory_usage(&self) -> usize {
+        if self.bounds.counts_bytes() {
+            self.bounds.used
+        } else {
+            self.measure_usage()
+        }
+    }
+
+    /// Inserts a key-value pair, returning the replaced value and every entry
+    /// evicted to make room.
+    ///
+    /// Behaves exactly like [`insert`](Self::insert), which evicts the same
+    /// entries but only reports them to the [`on_evict`](Self::on_evict)
+    /// callback. The new entry itself can be evicted, for example when it has
+    /// the smallest key under [`EvictionPolicy::SmallestKey`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::{Capacity, EvictionPolicy, SkipList};
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.set_capacity(Capacity::Entries(2), EvictionPolicy::LeastRecentlyUsed);
+    /// skiplist.insert(1, "one");
+    /// skiplist.insert(2, "two");
+    /// skiplist.get(&1);
+    ///
+    /// let outcome = skiplist.insert_evicting(3, "three");
+    /// assert_eq!(outcome.replaced, None);
+    /// assert_eq!(outcome.evicted, vec![(2, "two")]);
+    /// ```
+    pub fn insert_evicting(&mut self, key: K, value: V) -> Inserted<K, V> {
+        let replaced = self.insert_entry(key, value, &mut ());
+        Inserted { replaced, evicted: self.evict_to_capacity() }
+    }
+
+    /// Estimated bytes of every entry, by walking level 0
+    fn measure_usage(&self) -> usize {
+        let mut used = 0;
+        let mut next = self.head.read().unwrap().fwd[0].clone();
+        while let Some(node) = next {
+            let node_ref = node.read().unwrap();
+            if let (Some(key), Some(val)) = (&node_ref.key, &node_ref.val) {
+                used += self.bounds.entry_bytes(key, val, node_ref.height());
+            }
+            next = node_ref.fwd[0].clone();
+        }
+        used
+    }
+
+    /// Recompute byte usage and the recency index after a change that
+    /// bypassed the bookkeeping
+    pub(crate) fn recount_usage(&mut self) {
+        self.bounds.used = if self.bounds.counts_bytes() { self.measure_usage() } else { 0 };
+        self.reindex_recency();
+    }
+
+    /// Rebuild the recency index from the nodes' stamps, restamping them so
+    /// every stamp is unique while keeping their order (ties in key order)
+    fn reindex_recency(&mut self) {
+        let mut nodes = Vec::new();
+        if self.bounds.tracks_access() {
+            let mut next = self.head.read().unwrap().fwd[0].clone();
+            while let Some(node) = next {
+                let (stamp, after) = {
+                    let node_ref = node.read().unwrap();
+                    (node_ref.accessed.load(Ordering::Relaxed), node_ref.fwd[0].clone())
+                };
+                nodes.push((stamp, node));
+                next = after;
+            }
+            nodes.sort_by_key(|(stamp, _)| *stamp);
+        }
+
+        let recency = self.bounds.recency.get_mut().unwrap();
+        recency.clear();
+        for (_, node) in nodes {
+            let stamp = self.bounds.tick.fetch_add(1, Ordering::Relaxed) + 1;
+            node.read().unwrap().accessed.store(stamp, Ordering::Relaxed);
+            recency.insert(stamp, Arc::downgrade(&node));
+        }
+    }
+
+    /// Whether the list is over its capacity
+    fn over_capacity(&self) -> bool {
+        match self.bounds.capacity {
+            None => false,
+            Some(Capacity::Entries(max)) => self.len > max,
+            Some(Capacity::Bytes(max)) => self.bounds.used > max,
+        }
+    }
+
+    /// Evict entries until the list fits its capacity, returning them
+    pub(crate) fn evict_to_capacity(&mut self) -> Vec<(K, V)> {
+        let mut evicted = Vec::new();
+        while self.over_capacity() {
+            let Some(victim) = self.pick_victim() else { break };
+            let (height, back) = {
+                let victim_ref = victim.read().unwrap();
+                (victim_ref.height(), victim_ref.back.as_ref().and_then(|back| back.upgrade()))
+            };
+            let Some(back) = back else { break };
+            let update = Self::predecessors_from(&back, height);
+            let Some((key, val)) = self.unlink(&update, &victim) else { break };
+            if let Some(callback) = &mut self.bounds.on_evict {
+                callback(&key, &val);
+            }
+            evicted.push((key, val));
+        }
+        evicted
+    }
+
+    /// The entry the eviction policy gives up next
+    fn pick_victim(&mut self) -> Option<NodeRef<K, V>> {
+        match self.bounds.policy {
+            EvictionPolicy::SmallestKey => self.head.read().unwrap().fwd[0].clone(),
+            EvictionPolicy::LargestKey => self.tail.as_ref().and_then(|tail| tail.upgrade()),
+            EvictionPolicy::LeastRecentlyUsed => {
+                let recency = self.bounds.recency.get_mut().unwrap();
+                // Skip entries whose node is gone, unlinked or restamped since
+                while let Some((stamp, node)) = recency.pop_first() {
+                    let Some(node) = node.upgrade() else { continue };
+                    let current = {
+                        let node_ref = node.read().unwrap();
+                        node_ref.key.is_some() && node_ref.accessed.load(Ordering::Relaxed) == stamp
+                    };
+                    if current {
+                        recency.insert(stamp, Arc::downgrade(&node));
+                        return Some(node);
+                    }
+                }
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,399 @@
+//! `skiplist-rs bench`: drive a shared list from several threads and report
+//! throughput, latency percentiles and the list's final shape.
+//!
+//! ```text
+//! skiplist-rs bench --threads 8 --ops 10M --mix read=80,write=20 --dist zipf:0.99 --keys 1M
+//! ```
+
+use super::workload::{parse_count, Distribution, KeySpace, Mix, Op};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use skiplist_rs::{SkipList, SkipListStats};
+use std::error::Error;
+use std::fmt::Write as _;
+use std::sync::{Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const USAGE: &str = "\
+usage: skiplist-rs bench [options]
+
+  --threads <n>        worker threads (default 1)
+  --ops <n>            total operations, e.g. 500k or 10M (default 1M)
+  --mix <mix>          e.g. read=80,write=20,delete=0 (default read=80,write=20)
+  --dist <dist>        uniform | sequential | zipf[:theta] | hotspot[:keys,ops]
+                       (default uniform; zipf defaults to 0.99, hotspot to 0.2,0.8)
+  --keys <n>           size of the key space (default 100k)
+  --target <target>    rwlock (RwLock<SkipList>) | mutex (Mutex<SkipList>)
+                       (default rwlock)
+  --p <p>              level probability for the list (default 0.5)
+  --seed <n>           seed for tower heights and workload (default random)
+  --empty              start from an empty list instead of prefilling every key
+  --format <format>    text | json (default text)
+";
+
+/// Parsed `bench` options
+#[derive(Debug, Clone)]
+struct Options {
+    threads: usize,
+    ops: u64,
+    mix: Mix,
+    dist: Distribution,
+    keys: u64,
+    target: Target,
+    p: f64,
+    seed: u64,
+    prefill: bool,
+    json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    RwLock,
+    Mutex,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, Box<dyn Error>> {
+        let mut options = Options {
+            threads: 1,
+            ops: 1_000_000,
+            mix: Mix { read: 80, write: 20, delete: 0 },
+            dist: Distribution::Uniform,
+            keys: 100_000,
+            target: Target::RwLock,
+            p: 0.5,
+            seed: rand::thread_rng().gen(),
+            prefill: true,
+            json: false,
+        };
+
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            if flag == "--empty" {
+                options.prefill = false;
+                continue;
+            }
+            let value = args.next().ok_or_else(|| format!("`{}` needs a value", flag))?;
+            match flag.as_str() {
+                "--threads" => options.threads = parse_count(value)? as usize,
+                "--ops" => options.ops = parse_count(value)?,
+                "--mix" => options.mix = Mix::parse(value)?,
+                "--dist" => options.dist = Distribution::parse(value)?,
+                "--keys" => options.keys = parse_count(value)?,
+                "--target" => {
+                    options.target = match value.as_str() {
+                        "rwlock" => Target::RwLock,
+                        "mutex" => Target::Mutex,
+                        other => return Err(format!("unknown target `{}`", other).into()),
+                    }
+                }
+                "--p" => {
+                    options.p = value.parse().map_err(|_| format!("invalid probability `{}`", value))?;
+                    if !(0.0..1.0).contains(&options.p) {
+                        return Err("--p must be at least 0 and below 1".into());
+                    }
+                }
+                "--seed" => options.seed = value.parse().map_err(|_| format!("invalid seed `{}`", value))?,
+                "--format" => {
+                    options.json = match value.as_str() {
+                        "text" => false,
+                        "json" => true,
+                        other => return Err(format!("unknown format `{}`", other).into()),
+                    }
+                }
+                other => return Err(format!("unknown option `{}`\n\n{}", other, USAGE).into()),
+            }
+        }
+
+        if options.threads == 0 {
+            return Err("--threads must be at least 1".into());
+        }
+        if options.keys == 0 {
+            return Err("--keys must be at least 1".into());
+        }
+        Ok(options)
+    }
+}
+
+/// A thread-safe wrapper the workload can run against
+trait Shared: Sync {
+    fn get(&self, key: u64) -> Option<u64>;
+    fn insert(&self, key: u64, value: u64);
+    fn remove(&self, key: u64);
+    fn stats(&self) -> SkipListStats;
+}
+
+impl Shared for RwLock<SkipList<u64, u64>> {
+    fn get(&self, key: u64) -> Option<u64> {
+        self.read().unwrap().get(&key)
+    }
+
+    fn insert(&self, key: u64, value: u64) {
+        self.write().unwrap().insert(key, value);
+    }
+
+    fn remove(&self, key: u64) {
+        self.write().unwrap().remove(&key);
+    }
+
+    fn stats(&self) -> SkipListStats {
+        self.read().unwrap().stats()
+    }
+}
+
+impl Shared for Mutex<SkipList<u64, u64>> {
+    fn get(&self, key: u64) -> Option<u64> {
+        self.lock().unwrap().get(&key)
+    }
+
+    fn insert(&self, key: u64, value: u64) {
+        self.lock().unwrap().insert(key, value);
+    }
+
+    fn remove(&self, key: u64) {
+        self.lock().unwrap().remove(&key);
+    }
+
+    fn stats(&self) -> SkipListStats {
+        self.lock().unwrap().stats()
+    }
+}
+
+/// Sub-buckets per power of two; percentiles are accurate to 1/16
+const SUB_BUCKETS: u64 = 16;
+const BUCKETS: usize = 64 * SUB_BUCKETS as usize;
+
+/// Log-linear latency histogram in nanoseconds
+#[derive(Clone)]
+struct Histogram {
+    counts: Vec<u64>,
+    total: u64,
+    max: u64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Histogram { counts: vec![0; BUCKETS], total: 0, max: 0 }
+    }
+
+    fn bucket(nanos: u64) -> usize {
+        if nanos < SUB_BUCKETS {
+            return nanos as usize;
+        }
+        let exp = 63 - nanos.leading_zeros() as u64;
+        let sub = (nanos >> (exp - 4)) & (SUB_BUCKETS - 1);
+        ((exp - 3) * SUB_BUCKETS + sub) as usize
+    }
+
+    /// Smallest latency that falls into bucket `index`
+    fn lower_bound(index: usize) -> u64 {
+        let index = index as u64;
+        if index < SUB_BUCKETS {
+            return index;
+        }
+        let exp = index / SUB_BUCKETS + 3;
+        (SUB_BUCKETS + index % SUB_BUCKETS) << (exp - 4)
+    }
+
+    fn record(&mut self, latency: Duration) {
+        let nanos = latency.as_nanos().min(u64::MAX as u128) as u64;
+        self.counts[Self::bucket(nanos)] += 1;
+        self.total += 1;
+        self.max = self.max.max(nanos);
+    }
+
+    fn merge(&mut self, other: &Histogram) {
+        for (count, other) in self.counts.iter_mut().zip(&other.counts) {
+            *count += other;
+        }
+        self.total += other.total;
+        self.max = self.max.max(other.max);
+    }
+
+    /// Latency at quantile `q` (0 to 1), rounded down to its bucket
+    fn quantile(&self, q: f64) -> u64 {
+        let rank = ((self.total as f64 * q).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (index, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Self::lower_bound(index);
+            }
+        }
+        self.max
+    }
+}
+
+/// Latency percentiles reported, with their labels
+const PERCENTILES: [(&str, f64); 5] = [("p50", 0.5), ("p90", 0.9), ("p99", 0.99), ("p99.9", 0.999), ("p99.99", 0.9999)];
+
+/// Everything a run measured
+struct Report {
+    elapsed: Duration,
+    counts: [u64; 3],
+    latency: Histogram,
+    stats: SkipListStats,
+}
+
+/// Run the workload on `threads` threads against `shared`
+fn drive<S: Shared>(options: &Options, shared: &S) -> Report {
+    let space = KeySpace::new(&options.dist, options.keys);
+    let threads = options.threads;
+
+    let start = Instant::now();
+    let results: Vec<(Histogram, [u64; 3])> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|t| {
+                // Spread the remainder over the first threads
+                let ops = options.ops / threads as u64 + u64::from((t as u64) < options.ops % threads as u64);
+                let mut keys = space.generator(t, threads);
+                let mut rng = StdRng::seed_from_u64(options.seed.wrapping_add(t as u64 + 1));
+                scope.spawn(move || {
+                    let mut latency = Histogram::new();
+                    let mut counts = [0u64; 3];
+                    for _ in 0..ops {
+                        let op = options.mix.sample(&mut rng);
+                        let key = keys.next(&mut rng);
+                        let began = Instant::now();
+                        match op {
+                            Op::Read => {
+                                std::hint::black_box(shared.get(key));
+                            }
+                            Op::Write => shared.insert(key, key),
+                            Op::Delete => shared.remove(key),
+                        }
+                        latency.record(began.elapsed());
+                        counts[op as usize] += 1;
+                    }
+                    (latency, counts)
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    let elapsed = start.elapsed();
+
+    let mut latency = Histogram::new();
+    let mut counts = [0; 3];
+    for (histogram, thread_counts) in &results {
+        latency.merge(histogram);
+        for (total, count) in counts.iter_mut().zip(thread_counts) {
+            *total += count;
+        }
+    }
+    Report { elapsed, counts, latency, stats: shared.stats() }
+}
+
+/// The list every run starts from
+fn initial_list(options: &Options) -> SkipList<u64, u64> {
+    let mut list = SkipList::with_params(4, options.p);
+    list.seed(options.seed);
+    if options.prefill {
+        for key in 0..options.keys {
+            list.insert(key, key);
+        }
+    }
+    list
+}
+
+fn throughput(options: &Options, report: &Report) -> f64 {
+    options.ops as f64 / report.elapsed.as_secs_f64().max(f64::MIN_POSITIVE)
+}
+
+fn render_text(options: &Options, report: &Report) -> String {
+    let target = match options.target {
+        Target::RwLock => "RwLock<SkipList>",
+        Target::Mutex => "Mutex<SkipList>",
+    };
+    let mut out = String::new();
+    let _ = writeln!(out, "Target:          {}", target);
+    let _ = writeln!(out, "Threads:         {}", options.threads);
+    let _ = writeln!(
+        out,
+        "Operations:      {} (read {}, write {}, delete {})",
+        options.ops, report.counts[0], report.counts[1], report.counts[2]
+    );
+    let _ = writeln!(out, "Keys:            {} ({})", options.keys, options.dist);
+    let _ = writeln!(out, "Seed:            {}", options.seed);
+    let _ = writeln!(out, "Elapsed:         {:.3} s", report.elapsed.as_secs_f64());
+    let _ = writeln!(out, "Throughput:      {:.0} ops/s", throughput(options, report));
+    let percentiles: Vec<String> = PERCENTILES
+        .iter()
+        .map(|(label, q)| format!("{} {}", label, report.latency.quantile(*q)))
+        .collect();
+    let _ = writeln!(out, "Latency (ns):    {}, max {}", percentiles.join(", "), report.latency.max);
+    let _ = writeln!(out);
+    out.push_str(&report.stats.to_string());
+    out
+}
+
+fn render_json(options: &Options, report: &Report) -> String {
+    let target = match options.target {
+        Target::RwLock => "rwlock",
+        Target::Mutex => "mutex",
+    };
+    let percentiles: Vec<String> = PERCENTILES
+        .iter()
+        .map(|(label, q)| format!("\"{}\":{}", label, report.latency.quantile(*q)))
+        .collect();
+    let stats = &report.stats;
+    let levels: Vec<String> = stats.nodes_per_level.iter().map(|n| n.to_string()).collect();
+
+    format!(
+        concat!(
+            "{{\"target\":\"{}\",\"threads\":{},\"ops\":{},",
+            "\"counts\":{{\"read\":{},\"write\":{},\"delete\":{}}},",
+            "\"keys\":{},\"dist\":\"{}\",\"seed\":{},",
+            "\"elapsed_secs\":{:.6},\"ops_per_sec\":{:.1},",
+            "\"latency_ns\":{{{},\"max\":{}}},",
+            "\"stats\":{{\"len\":{},\"max_level\":{},\"optimal_levels\":{},\"nodes_per_level\":[{}],",
+            "\"avg_height\":{:.4},\"max_height\":{},\"node_bytes\":{},",
+            "\"avg_comparisons\":{:.4},\"max_comparisons\":{}}}}}\n"
+        ),
+        target,
+        options.threads,
+        options.ops,
+        report.counts[0],
+        report.counts[1],
+        report.counts[2],
+        options.keys,
+        options.dist,
+        options.seed,
+        report.elapsed.as_secs_f64(),
+        throughput(options, report),
+        percentiles.join(","),
+        report.latency.max,
+        stats.len,
+        stats.max_level,
+        stats.optimal_levels,
+        levels.join(","),
+        stats.avg_height,
+        stats.max_height,
+        stats.node_bytes,
+        stats.avg_comparisons,
+        stats.max_comparisons,
+    )
+}
+
+/// Entry point for `skiplist-rs bench`
+pub fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        print!("{}", USAGE);
+        return Ok(());
+    }
+    let options = Options::parse(args)?;
+    let list = initial_list(&options);
+
+    let report = match options.target {
+        Target::RwLock => drive(&options, &RwLock::new(list)),
+        Target::Mutex => drive(&options, &Mutex::new(list)),
+    };
+
+    if options.json {
+        print!("{}", render_json(&options, &report));
+    } else {
+        print!("{}", render_text(&options, &report));
+    }
+    Ok(())
+}
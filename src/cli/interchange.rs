@@ -0,0 +1,222 @@
+//! `skiplist-rs import` and `skiplist-rs export`: convert between CSV or
+//! JSON lines and snapshots.
+//!
+//! ```text
+//! skiplist-rs import users.csv users.skpl --key string --duplicates error
+//! skiplist-rs export users.skpl - --format jsonl | head
+//! ```
+//!
+//! Both directions stream: records are inserted as they are parsed and the
+//! snapshot is written entry by entry, so a large file is never held in
+//! memory next to the list built from it.
+
+use skiplist_rs::{
+    DuplicatePolicy, ExportOptions, Format, ImportOptions, ImportSummary, SkipList, SnapshotValue, TextKey,
+};
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+pub const IMPORT_USAGE: &str = "\
+usage: skiplist-rs import <input> <snapshot> [options]
+
+  --format <format>    csv | jsonl (default: from the input's extension)
+  --key <type>         int | string | bytes (hex) (default int)
+  --duplicates <how>   overwrite | keep-first | error (default overwrite)
+  --no-header          the first CSV record is data, not a header
+
+<input> may be `-` for stdin.
+";
+
+pub const EXPORT_USAGE: &str = "\
+usage: skiplist-rs export <snapshot> <output> [options]
+
+  --format <format>    csv | jsonl (default: from the output's extension)
+  --key <type>         int | string | bytes (hex); must match the import
+                       (default int)
+  --no-header          leave out the CSV header
+
+<output> may be `-` for stdout.
+";
+
+/// How keys are typed in the snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyType {
+    Int,
+    String,
+    Bytes,
+}
+
+/// Options shared by both directions
+#[derive(Debug)]
+struct Options {
+    /// The text file (import input or export output)
+    text: String,
+    snapshot: String,
+    format: Format,
+    key: KeyType,
+    duplicates: DuplicatePolicy,
+    header: bool,
+}
+
+impl Options {
+    /// Parse the arguments; `text_first` says whether the text file comes
+    /// before the snapshot
+    fn parse(args: &[String], text_first: bool, usage: &str) -> Result<Self, Box<dyn Error>> {
+        let mut paths = Vec::new();
+        let mut format = None;
+        let mut key = KeyType::Int;
+        let mut duplicates = DuplicatePolicy::Overwrite;
+        let mut header = true;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--no-header" {
+                header = false;
+                continue;
+            }
+            if !arg.starts_with("--") {
+                paths.push(arg.clone());
+                continue;
+            }
+            let value = args.next().ok_or_else(|| format!("`{}` needs a value", arg))?;
+            match arg.as_str() {
+                "--format" => format = Some(parse_format(value)?),
+                "--key" => {
+                    key = match value.as_str() {
+                        "int" => KeyType::Int,
+                        "string" => KeyType::String,
+                        "bytes" => KeyType::Bytes,
+                        other => return Err(format!("unknown key type `{}`", other).into()),
+                    }
+                }
+                "--duplicates" if text_first => {
+                    duplicates = match value.as_str() {
+                        "overwrite" => DuplicatePolicy::Overwrite,
+                        "keep-first" => DuplicatePolicy::KeepFirst,
+                        "error" => DuplicatePolicy::Reject,
+                        other => return Err(format!("unknown duplicate policy `{}`", other).into()),
+                    }
+                }
+                other => return Err(format!("unknown option `{}`\n\n{}", other, usage).into()),
+            }
+        }
+
+        let [first, second] = <[String; 2]>::try_from(paths)
+            .map_err(|_| format!("expected two paths\n\n{}", usage))?;
+        let (text, snapshot) = if text_first { (first, second) } else { (second, first) };
+        let format = match format {
+            Some(format) => format,
+            None => infer_format(&text)?,
+        };
+        Ok(Options { text, snapshot, format, key, duplicates, header })
+    }
+}
+
+fn parse_format(text: &str) -> Result<Format, Box<dyn Error>> {
+    match text {
+        "csv" => Ok(Format::Csv),
+        "jsonl" | "ndjson" => Ok(Format::JsonLines),
+        other => Err(format!("unknown format `{}`", other).into()),
+    }
+}
+
+/// Pick the format from a file extension
+fn infer_format(path: &str) -> Result<Format, Box<dyn Error>> {
+    let extension = Path::new(path).extension().and_then(|ext| ext.to_str());
+    extension
+        .and_then(|ext| parse_format(&ext.to_ascii_lowercase()).ok())
+        .ok_or_else(|| format!("cannot tell the format of `{}`; pass --format csv or --format jsonl", path).into())
+}
+
+fn open_input(path: &str) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
+    if path == "-" {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let file = File::open(path).map_err(|err| format!("cannot open `{}`: {}", path, err))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+fn open_output(path: &str) -> Result<Box<dyn Write>, Box<dyn Error>> {
+    if path == "-" {
+        return Ok(Box::new(io::stdout().lock()));
+    }
+    let file = File::create(path).map_err(|err| format!("cannot create `{}`: {}", path, err))?;
+    Ok(Box::new(BufWriter::new(file)))
+}
+
+/// Import `options.text` into a snapshot keyed by `K`
+fn import_as<K>(options: &Options) -> Result<ImportSummary, Box<dyn Error>>
+where
+    K: Ord + Clone + TextKey + SnapshotValue,
+{
+    let mut list: SkipList<K, String> = SkipList::new();
+    let import_options = ImportOptions {
+        format: options.format,
+        duplicates: options.duplicates,
+        header: options.header,
+    };
+    let summary = list
+        .import(open_input(&options.text)?, &import_options)
+        .map_err(|err| format!("{}: {}", options.text, err))?;
+
+    let mut output = open_output(&options.snapshot)?;
+    list.write_snapshot(&mut output)?;
+    output.flush()?;
+    Ok(summary)
+}
+
+/// Export the snapshot keyed by `K` to `options.text`
+fn export_as<K>(options: &Options) -> Result<u64, Box<dyn Error>>
+where
+    K: Ord + Clone + TextKey + SnapshotValue,
+{
+    let list: SkipList<K, String> = SkipList::read_snapshot(open_input(&options.snapshot)?)
+        .map_err(|err| format!("{}: {}", options.snapshot, err))?;
+
+    let mut output = open_output(&options.text)?;
+    let export_options = ExportOptions { format: options.format, header: options.header };
+    let written = list.export(&mut output, &export_options)?;
+    output.flush()?;
+    Ok(written)
+}
+
+/// `skiplist-rs import`
+pub fn run_import(args: &[String]) -> Result<(), Box<dyn Error>> {
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        print!("{}", IMPORT_USAGE);
+        return Ok(());
+    }
+    let options = Options::parse(args, true, IMPORT_USAGE)?;
+    let summary = match options.key {
+        KeyType::Int => import_as::<i64>(&options)?,
+        KeyType::String => import_as::<String>(&options)?,
+        KeyType::Bytes => import_as::<Vec<u8>>(&options)?,
+    };
+    eprintln!(
+        "imported {} records into {}: {} inserted, {} overwritten, {} skipped",
+        summary.records(),
+        options.snapshot,
+        summary.inserted,
+        summary.overwritten,
+        summary.skipped
+    );
+    Ok(())
+}
+
+/// `skiplist-rs export`
+pub fn run_export(args: &[String]) -> Result<(), Box<dyn Error>> {
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        print!("{}", EXPORT_USAGE);
+        return Ok(());
+    }
+    let options = Options::parse(args, false, EXPORT_USAGE)?;
+    let written = match options.key {
+        KeyType::Int => export_as::<i64>(&options)?,
+        KeyType::String => export_as::<String>(&options)?,
+        KeyType::Bytes => export_as::<Vec<u8>>(&options)?,
+    };
+    eprintln!("exported {} entries from {}", written, options.snapshot);
+    Ok(())
+}
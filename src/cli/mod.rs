@@ -0,0 +1,42 @@
+//! Subcommands of the `skiplist-rs` binary.
+
+mod bench;
+mod interchange;
+mod repl;
+mod workload;
+
+use std::error::Error;
+use std::io::{self, IsTerminal};
+
+const USAGE: &str = "\
+usage: skiplist-rs [repl]           interactive shell (the default)
+       skiplist-rs bench [options]  multi-threaded load test (see `bench --help`)
+       skiplist-rs import <input> <snapshot> [options]
+                                    load CSV or JSON lines into a snapshot
+       skiplist-rs export <snapshot> <output> [options]
+                                    write a snapshot out as CSV or JSON lines
+       skiplist-rs help             show this message
+
+Type `help` inside the shell for its commands. Commands can also be piped in:
+
+    printf 'seed 1\\nput 1 one\\nshow\\n' | skiplist-rs
+";
+
+/// Dispatch on the first command-line argument
+pub fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    match args.first().map(String::as_str) {
+        None | Some("repl") => {
+            let stdin = io::stdin();
+            let interactive = stdin.is_terminal();
+            repl::run(stdin.lock(), io::stdout().lock(), interactive)
+        }
+        Some("bench") => bench::run(&args[1..]),
+        Some("import") => interchange::run_import(&args[1..]),
+        Some("export") => interchange::run_export(&args[1..]),
+        Some("help" | "-h" | "--help") => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        Some(other) => Err(format!("unknown command `{}`\n\n{}", other, USAGE).into()),
+    }
+}
@@ -0,0 +1,179 @@
+//! Interactive shell over a `SkipList<i64, String>`.
+//!
+//! Every command is one line; output goes to the writer and errors are
+//! reported inline so a script of commands keeps running past a bad line.
+//! With `seed` and `save`/`load` a session can reproduce the exact tower
+//! layout a bug was seen with.
+
+use skiplist_rs::{RenderOptions, SkipList};
+use std::error::Error;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{BufRead, Write};
+
+/// Entries drawn by `show` unless a count is given
+const SHOW_DEFAULT: usize = 32;
+
+const HELP: &str = "\
+commands:
+  put <key> <value>    insert or update (value is the rest of the line)
+  get <key>            look up a key
+  del <key>            remove a key
+  range [a] [b]        entries with a <= key <= b (open-ended when omitted)
+  len                  number of entries
+  stats                structural statistics
+  show [n]             draw the towers of the first n entries (default 32)
+  check                verify the structural invariants
+  seed <n>             make tower heights reproducible from now on
+  save <file>          write a snapshot, tower heights included
+  load <file>          replace the list with a snapshot
+  clear                remove every entry
+  help                 show this message
+  quit                 leave the shell
+keys are 64-bit signed integers";
+
+type CommandResult = Result<String, Box<dyn Error>>;
+
+/// The list being explored and the seed it was given
+struct Repl {
+    list: SkipList<i64, String>,
+    seed: Option<u64>,
+}
+
+/// What to do after a line has run
+enum Flow {
+    Continue(String),
+    Quit,
+}
+
+fn parse_key(arg: Option<&str>) -> Result<i64, Box<dyn Error>> {
+    let arg = arg.ok_or("missing key")?;
+    arg.parse().map_err(|_| format!("invalid key `{}`", arg).into())
+}
+
+impl Repl {
+    fn new() -> Self {
+        Repl { list: SkipList::new(), seed: None }
+    }
+
+    /// Run one line of input
+    fn execute(&mut self, line: &str) -> Result<Flow, Box<dyn Error>> {
+        let line = line.trim();
+        let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim_start();
+        let mut args = rest.split_whitespace();
+
+        let output = match command {
+            "" => String::new(),
+            "put" => {
+                let key = parse_key(args.next())?;
+                let value = rest.split_once(char::is_whitespace).map(|(_, v)| v.trim_start());
+                let value = value.filter(|v| !v.is_empty()).ok_or("missing value")?;
+                match self.list.insert(key, value.to_string()) {
+                    Some(old) => format!("updated (was {})", old),
+                    None => "inserted".to_string(),
+                }
+            }
+            "get" => match self.list.get(&parse_key(args.next())?) {
+                Some(value) => value,
+                None => "(not found)".to_string(),
+            },
+            "del" => match self.list.remove(&parse_key(args.next())?) {
+                Some(value) => format!("removed {}", value),
+                None => "(not found)".to_string(),
+            },
+            "range" => self.range(args.next(), args.next())?,
+            "len" => self.list.len().to_string(),
+            "stats" => self.list.stats().to_string().trim_end().to_string(),
+            "show" => {
+                let count = match args.next() {
+                    Some(n) => n.parse().map_err(|_| format!("invalid count `{}`", n))?,
+                    None => SHOW_DEFAULT,
+                };
+                let drawing = self.list.to_ascii_with(&RenderOptions::with_max_nodes(count));
+                drawing.trim_end().to_string()
+            }
+            "check" => match self.list.check_invariants() {
+                Ok(()) => "ok".to_string(),
+                Err(err) => format!("invariant violated: {}", err),
+            },
+            "seed" => {
+                let arg = args.next().ok_or("missing seed")?;
+                let seed = arg.parse().map_err(|_| format!("invalid seed `{}`", arg))?;
+                self.list.seed(seed);
+                self.seed = Some(seed);
+                format!("seeded with {}", seed)
+            }
+            "save" => {
+                let path = args.next().ok_or("missing file name")?;
+                fs::write(path, self.list.to_snapshot())?;
+                format!("saved {} entries to {}", self.list.len(), path)
+            }
+            "load" => {
+                let path = args.next().ok_or("missing file name")?;
+                let bytes = fs::read(path)?;
+                self.list = SkipList::from_snapshot(&bytes)?;
+                if let Some(seed) = self.seed {
+                    self.list.seed(seed);
+                }
+                format!("loaded {} entries from {}", self.list.len(), path)
+            }
+            "clear" => {
+                let count = self.list.drain().count();
+                format!("removed {} entries", count)
+            }
+            "help" => HELP.to_string(),
+            "quit" | "exit" => return Ok(Flow::Quit),
+            other => return Err(format!("unknown command `{}` (try `help`)", other).into()),
+        };
+        Ok(Flow::Continue(output))
+    }
+
+    /// List the entries between two optional inclusive bounds
+    fn range(&self, start: Option<&str>, end: Option<&str>) -> CommandResult {
+        let start = start.map(|s| parse_key(Some(s))).transpose()?.unwrap_or(i64::MIN);
+        let end = end.map(|s| parse_key(Some(s))).transpose()?.unwrap_or(i64::MAX);
+
+        let mut out = String::new();
+        let mut count = 0;
+        for (key, value) in self.list.range(start..=end) {
+            let _ = writeln!(out, "{} => {}", key, value);
+            count += 1;
+        }
+        let _ = write!(out, "({} entries)", count);
+        Ok(out)
+    }
+}
+
+/// Read commands from `input` until it ends or `quit` is entered.
+///
+/// The banner and prompts are only written when `interactive` is set, so
+/// piped scripts produce nothing but command output.
+pub fn run<R, W>(input: R, mut output: W, interactive: bool) -> Result<(), Box<dyn Error>>
+where
+    R: BufRead,
+    W: Write,
+{
+    let mut repl = Repl::new();
+    if interactive {
+        writeln!(output, "skiplist-rs shell; type `help` for commands")?;
+    }
+
+    let mut lines = input.lines();
+    loop {
+        if interactive {
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+        let Some(line) = lines.next() else {
+            break;
+        };
+        match repl.execute(&line?) {
+            Ok(Flow::Continue(text)) if text.is_empty() => {}
+            Ok(Flow::Continue(text)) => writeln!(output, "{}", text)?,
+            Ok(Flow::Quit) => break,
+            Err(err) => writeln!(output, "error: {}", err)?,
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,241 @@
+//! Operation mixes and key distributions for the `bench` subcommand.
+
+use rand::Rng;
+use std::error::Error;
+use std::fmt;
+
+/// Parse a count such as `250`, `10k`, `10M` or `1G`
+pub fn parse_count(text: &str) -> Result<u64, Box<dyn Error>> {
+    let (digits, scale) = match text.char_indices().last() {
+        Some((i, 'k' | 'K')) => (&text[..i], 1_000),
+        Some((i, 'm' | 'M')) => (&text[..i], 1_000_000),
+        Some((i, 'g' | 'G')) => (&text[..i], 1_000_000_000),
+        _ => (text, 1),
+    };
+    let count: u64 = digits.parse().map_err(|_| format!("invalid count `{}`", text))?;
+    count.checked_mul(scale).ok_or_else(|| format!("count `{}` is too large", text).into())
+}
+
+/// One benchmark operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Read,
+    Write,
+    Delete,
+}
+
+/// Percentages of reads, writes and deletes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mix {
+    pub read: u32,
+    pub write: u32,
+    pub delete: u32,
+}
+
+impl Mix {
+    /// Parse `read=80,write=20` (missing kinds are 0; the total must be 100)
+    pub fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut mix = Mix { read: 0, write: 0, delete: 0 };
+        for part in text.split(',') {
+            let (kind, percent) = part.split_once('=').ok_or_else(|| format!("invalid mix entry `{}`", part))?;
+            let percent: u32 = match percent.parse() {
+                Ok(percent) if percent <= 100 => percent,
+                _ => return Err(format!("invalid percentage `{}`", percent).into()),
+            };
+            match kind.trim() {
+                "read" => mix.read = percent,
+                "write" => mix.write = percent,
+                "delete" => mix.delete = percent,
+                other => return Err(format!("unknown operation `{}` in mix", other).into()),
+            }
+        }
+        if mix.read + mix.write + mix.delete != 100 {
+            return Err(format!("mix `{}` does not add up to 100", text).into());
+        }
+        Ok(mix)
+    }
+
+    /// Pick the next operation
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Op {
+        let roll = rng.gen_range(0..100);
+        if roll < self.read {
+            Op::Read
+        } else if roll < self.read + self.write {
+            Op::Write
+        } else {
+            Op::Delete
+        }
+    }
+}
+
+/// How keys are drawn from `0..keys`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Distribution {
+    /// Every key equally likely
+    Uniform,
+    /// Each thread walks the key space in order from its own starting point
+    Sequential,
+    /// Zipfian with exponent `theta`: key 0 is the most popular
+    Zipf { theta: f64 },
+    /// `ops` of the operations go to the first `keys` fraction of the key space
+    Hotspot { keys: f64, ops: f64 },
+}
+
+impl Distribution {
+    /// Parse `uniform`, `sequential`, `zipf:0.99` or `hotspot:0.2,0.8`
+    pub fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+        let (name, arg) = text.split_once(':').unwrap_or((text, ""));
+        let fraction = |s: &str| -> Result<f64, Box<dyn Error>> {
+            let value: f64 = s.parse().map_err(|_| format!("invalid number `{}`", s))?;
+            if (0.0..=1.0).contains(&value) {
+                Ok(value)
+            } else {
+                Err(format!("`{}` is not between 0 and 1", s).into())
+            }
+        };
+
+        match name {
+            "uniform" => Ok(Distribution::Uniform),
+            "sequential" => Ok(Distribution::Sequential),
+            "zipf" => {
+                let theta = if arg.is_empty() { 0.99 } else { fraction(arg)? };
+                if theta == 0.0 || theta == 1.0 {
+                    return Err("zipf exponent must be strictly between 0 and 1".into());
+                }
+                Ok(Distribution::Zipf { theta })
+            }
+            "hotspot" => {
+                let (keys, ops) = match arg {
+                    "" => ("0.2", "0.8"),
+                    arg => arg.split_once(',').ok_or("hotspot takes `key fraction,op fraction`")?,
+                };
+                Ok(Distribution::Hotspot { keys: fraction(keys)?, ops: fraction(ops)? })
+            }
+            other => Err(format!("unknown distribution `{}`", other).into()),
+        }
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Distribution::Uniform => write!(f, "uniform"),
+            Distribution::Sequential => write!(f, "sequential"),
+            Distribution::Zipf { theta } => write!(f, "zipf:{}", theta),
+            Distribution::Hotspot { keys, ops } => write!(f, "hotspot:{},{}", keys, ops),
+        }
+    }
+}
+
+/// Precomputed constants for drawing Zipfian keys in O(1).
+///
+/// This is the generator from Gray et al., "Quickly Generating Billion-Record
+/// Synthetic Databases" (the one YCSB uses); it needs one O(n) pass to sum
+/// the zeta series up front.
+#[derive(Debug, Clone)]
+struct Zipf {
+    n: f64,
+    theta: f64,
+    alpha: f64,
+    zetan: f64,
+    eta: f64,
+}
+
+impl Zipf {
+    fn new(n: u64, theta: f64) -> Self {
+        let zeta = |count: u64| (1..=count).map(|i| 1.0 / (i as f64).powf(theta)).sum::<f64>();
+        let zetan = zeta(n);
+        let zeta2 = zeta(2.min(n));
+        let n = n as f64;
+        Zipf {
+            n,
+            theta,
+            alpha: 1.0 / (1.0 - theta),
+            zetan,
+            eta: (1.0 - (2.0 / n).powf(1.0 - theta)) / (1.0 - zeta2 / zetan),
+        }
+    }
+
+    fn sample<R: Rng>(&self, rng: &mut R) -> u64 {
+        let u: f64 = rng.gen();
+        let uz = u * self.zetan;
+        if uz < 1.0 {
+            return 0;
+        }
+        if uz < 1.0 + 0.5f64.powf(self.theta) {
+            return 1;
+        }
+        let key = self.n * (self.eta * u - self.eta + 1.0).powf(self.alpha);
+        (key as u64).min(self.n as u64 - 1)
+    }
+}
+
+/// A key space of `0..keys` with its distribution, ready to hand out
+/// per-thread generators
+pub struct KeySpace {
+    keys: u64,
+    kind: KeyGenKind,
+}
+
+impl KeySpace {
+    /// Prepare `dist` over `0..keys` (for Zipf, this sums the zeta series once)
+    pub fn new(dist: &Distribution, keys: u64) -> Self {
+        let kind = match *dist {
+            Distribution::Uniform => KeyGenKind::Uniform,
+            Distribution::Sequential => KeyGenKind::Sequential { next: 0 },
+            Distribution::Zipf { theta } => KeyGenKind::Zipf(Zipf::new(keys, theta)),
+            Distribution::Hotspot { keys: fraction, ops } => KeyGenKind::Hotspot {
+                hot: ((keys as f64 * fraction) as u64).clamp(1, keys),
+                ops,
+            },
+        };
+        KeySpace { keys, kind }
+    }
+
+    /// The generator for thread `thread` of `threads`
+    pub fn generator(&self, thread: usize, threads: usize) -> KeyGen {
+        let kind = match &self.kind {
+            KeyGenKind::Sequential { .. } => KeyGenKind::Sequential {
+                next: self.keys / threads as u64 * thread as u64,
+            },
+            kind => kind.clone(),
+        };
+        KeyGen { keys: self.keys, kind }
+    }
+}
+
+/// Draws keys for one thread
+pub struct KeyGen {
+    keys: u64,
+    kind: KeyGenKind,
+}
+
+#[derive(Clone)]
+enum KeyGenKind {
+    Uniform,
+    Sequential { next: u64 },
+    Zipf(Zipf),
+    Hotspot { hot: u64, ops: f64 },
+}
+
+impl KeyGen {
+    /// Draw the next key
+    pub fn next<R: Rng>(&mut self, rng: &mut R) -> u64 {
+        match &mut self.kind {
+            KeyGenKind::Uniform => rng.gen_range(0..self.keys),
+            KeyGenKind::Sequential { next } => {
+                let key = *next;
+                *next = (*next + 1) % self.keys;
+                key
+            }
+            KeyGenKind::Zipf(zipf) => zipf.sample(rng),
+            KeyGenKind::Hotspot { hot, ops } => {
+                if *hot == self.keys || rng.gen_bool(*ops) {
+                    rng.gen_range(0..*hot)
+                } else {
+                    rng.gen_range(*hot..self.keys)
+                }
+            }
+        }
+    }
+}
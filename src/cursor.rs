@@ -0,0 +1,331 @@
+//! Cursors for walking and editing a [`SkipList`] in place.
+//!
+//! A cursor sits on one entry of the list and moves along level 0 one step at a
+//! time in either direction. Like the cursors on `std::collections::LinkedList`,
+//! there is also a "ghost" position between the last and the first entry; in
+//! this list the ghost is the head node itself.
+//!
+//! Because every node keeps a back link at level 0, moving backwards is as
+//! cheap as moving forwards, and [`CursorMut`] can insert or remove at its
+//! position by walking back to the predecessors it needs instead of searching
+//! again from the head.
+
+use crate::{NodeRef, SkipList};
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Weak};
+
+/// Error returned by [`CursorMut::insert_after`] when the key would break the
+/// list's ordering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnorderedKeyError;
+
+impl fmt::Display for UnorderedKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "key is not ordered between the cursor and its neighbor")
+    }
+}
+
+impl Error for UnorderedKeyError {}
+
+/// Step from `node` to its level-0 neighbor, wrapping through the ghost.
+fn step_next<K, V>(head: &NodeRef<K, V>, node: &NodeRef<K, V>) -> NodeRef<K, V> {
+    let next = node.read().unwrap().fwd[0].clone();
+    next.unwrap_or_else(|| Arc::clone(head))
+}
+
+/// Clone the entry stored in `node`, or `None` for the ghost.
+fn entry_of<K: Clone, V: Clone>(node: &NodeRef<K, V>) -> Option<(K, V)> {
+    let node_ref = node.read().unwrap();
+    Some((node_ref.key.clone()?, node_ref.val.clone()?))
+}
+
+/// A read-only cursor over a [`SkipList`].
+///
+/// Created by [`SkipList::cursor_front`] or [`SkipList::lower_bound_cursor`].
+pub struct Cursor<'a, K, V> {
+    list: &'a SkipList<K, V>,
+    current: NodeRef<K, V>,
+}
+
+impl<K, V> Cursor<'_, K, V>
+where
+    K: Ord,
+{
+    /// Returns true if the cursor is on the ghost position.
+    pub fn is_ghost(&self) -> bool {
+        Arc::ptr_eq(&self.current, &self.list.head)
+    }
+
+    /// Returns the entry under the cursor, or `None` on the ghost.
+    pub fn current(&self) -> Option<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        entry_of(&self.current)
+    }
+
+    /// Returns the key under the cursor, or `None` on the ghost.
+    pub fn key(&self) -> Option<K>
+    where
+        K: Clone,
+    {
+        self.current.read().unwrap().key.clone()
+    }
+
+    /// Moves to the next entry; from the last entry this moves to the ghost,
+    /// and from the ghost to the first entry.
+    pub fn move_next(&mut self) {
+        self.current = step_next(&self.list.head, &self.current);
+    }
+
+    /// Moves to the previous entry; from the first entry this moves to the
+    /// ghost, and from the ghost to the last entry.
+    pub fn move_prev(&mut self) {
+        self.current = self.list.step_prev(&self.current);
+    }
+
+    /// Returns the entry after the cursor without moving.
+    pub fn peek_next(&self) -> Option<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        entry_of(&step_next(&self.list.head, &self.current))
+    }
+
+    /// Returns the entry before the cursor without moving.
+    pub fn peek_prev(&self) -> Option<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        entry_of(&self.list.step_prev(&self.current))
+    }
+}
+
+/// A cursor over a [`SkipList`] that can edit the list at its position.
+///
+/// Created by [`SkipList::cursor_front_mut`] or [`SkipList::lower_bound_cursor_mut`].
+pub struct CursorMut<'a, K, V> {
+    list: &'a mut SkipList<K, V>,
+    current: NodeRef<K, V>,
+}
+
+impl<K, V> CursorMut<'_, K, V>
+where
+    K: Ord,
+{
+    /// Returns true if the cursor is on the ghost position.
+    pub fn is_ghost(&self) -> bool {
+        Arc::ptr_eq(&self.current, &self.list.head)
+    }
+
+    /// Returns the entry under the cursor, or `None` on the ghost.
+    pub fn current(&self) -> Option<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        entry_of(&self.current)
+    }
+
+    /// Returns the key under the cursor, or `None` on the ghost.
+    pub fn key(&self) -> Option<K>
+    where
+        K: Clone,
+    {
+        self.current.read().unwrap().key.clone()
+    }
+
+    /// Replaces the value under the cursor, returning the old one.
+    ///
+    /// Does nothing and returns `None` on the ghost.
+    pub fn set_value(&mut self, value: V) -> Option<V> {
+        if self.is_ghost() {
+            return None;
+        }
+        record!(self.list, updates);
+        let mut current = self.current.write().unwrap();
+        let old = current.val.replace(value);
+        if let Some(old) = &old {
+            self.list.bounds.updated(&current, old);
+        }
+        old
+    }
+
+    /// Moves to the next entry, wrapping through the ghost.
+    pub fn move_next(&mut self) {
+        self.current = step_next(&self.list.head, &self.current);
+    }
+
+    /// Moves to the previous entry, wrapping through the ghost.
+    pub fn move_prev(&mut self) {
+        self.current = self.list.step_prev(&self.current);
+    }
+
+    /// Returns the entry after the cursor without moving.
+    pub fn peek_next(&self) -> Option<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        entry_of(&step_next(&self.list.head, &self.current))
+    }
+
+    /// Returns the entry before the cursor without moving.
+    pub fn peek_prev(&self) -> Option<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        entry_of(&self.list.step_prev(&self.current))
+    }
+
+    /// Inserts a new entry right after the cursor without moving it.
+    ///
+    /// On the ghost the entry becomes the new first element. The key must sort
+    /// strictly between the current entry and the next one, otherwise
+    /// [`UnorderedKeyError`] is returned and the list is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.insert(1, "one");
+    /// skiplist.insert(3, "three");
+    ///
+    /// let mut cursor = skiplist.lower_bound_cursor_mut(&1);
+    /// assert!(cursor.insert_after(2, "two").is_ok());
+    /// assert!(cursor.insert_after(5, "five").is_err());
+    ///
+    /// assert_eq!(skiplist.get(&2), Some("two"));
+    /// ```
+    pub fn insert_after(&mut self, key: K, value: V) -> Result<(), UnorderedKeyError> {
+        {
+            let current = self.current.read().unwrap();
+            if current.key.as_ref().is_some_and(|k| k >= &key) {
+                return Err(UnorderedKeyError);
+            }
+            if let Some(next) = &current.fwd[0] {
+                if next.read().unwrap().key.as_ref().is_some_and(|k| k <= &key) {
+                    return Err(UnorderedKeyError);
+                }
+            }
+        }
+
+        let height = self.list.random_level();
+        let update = SkipList::predecessors_from(&self.current, height);
+        self.list.link_after(&update, key, value);
+        Ok(())
+    }
+
+    /// Removes the entry under the cursor and moves to the next one.
+    ///
+    /// Returns `None` and does nothing on the ghost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.insert(1, "one");
+    /// skiplist.insert(2, "two");
+    ///
+    /// let mut cursor = skiplist.cursor_front_mut();
+    /// assert_eq!(cursor.remove_current(), Some((1, "one")));
+    /// assert_eq!(cursor.key(), Some(2));
+    /// assert_eq!(skiplist.len(), 1);
+    /// ```
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        if self.is_ghost() {
+            return None;
+        }
+
+        let node = Arc::clone(&self.current);
+        let (height, back) = {
+            let node_ref = node.read().unwrap();
+            (node_ref.height(), node_ref.back.as_ref().and_then(Weak::upgrade))
+        };
+        let update = SkipList::predecessors_from(&back?, height);
+
+        self.current = step_next(&self.list.head, &node);
+        self.list.unlink(&update, &node)
+    }
+}
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord,
+{
+    /// Step from `node` to its level-0 predecessor, wrapping through the ghost
+    fn step_prev(&self, node: &NodeRef<K, V>) -> NodeRef<K, V> {
+        if Arc::ptr_eq(node, &self.head) {
+            return self.last_node();
+        }
+        let back = node.read().unwrap().back.as_ref().and_then(Weak::upgrade);
+        back.unwrap_or_else(|| Arc::clone(&self.head))
+    }
+
+    /// Find the first node whose key is not less than `key`, or the head
+    fn lower_bound_node(&self, key: &K) -> NodeRef<K, V> {
+        let update = self.find_predecessors(key);
+        let next = update[0].read().unwrap().fwd[0].clone();
+        next.unwrap_or_else(|| Arc::clone(&self.head))
+    }
+
+    /// Returns a cursor on the first entry (or the ghost if the list is empty).
+    pub fn cursor_front(&self) -> Cursor<'_, K, V> {
+        Cursor {
+            current: step_next(&self.head, &self.head),
+            list: self,
+        }
+    }
+
+    /// Returns a mutable cursor on the first entry (or the ghost if the list is empty).
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, K, V> {
+        CursorMut {
+            current: step_next(&self.head, &self.head),
+            list: self,
+        }
+    }
+
+    /// Returns a cursor on the first entry whose key is at least `key`.
+    ///
+    /// If every key is smaller the cursor starts on the ghost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.insert(10, "ten");
+    /// skiplist.insert(20, "twenty");
+    ///
+    /// let mut cursor = skiplist.lower_bound_cursor(&15);
+    /// assert_eq!(cursor.key(), Some(20));
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.key(), Some(10));
+    /// ```
+    pub fn lower_bound_cursor(&self, key: &K) -> Cursor<'_, K, V> {
+        Cursor {
+            current: self.lower_bound_node(key),
+            list: self,
+        }
+    }
+
+    /// Returns a mutable cursor on the first entry whose key is at least `key`.
+    ///
+    /// If every key is smaller the cursor starts on the ghost.
+    pub fn lower_bound_cursor_mut(&mut self, key: &K) -> CursorMut<'_, K, V> {
+        CursorMut {
+            current: self.lower_bound_node(key),
+            list: self,
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! Bulk removal: `retain`, `drain`, `drain_range` and `extract_if`.
+//!
+//! All of these walk level 0 once, left to right. While walking they keep a
+//! running predecessor vector: for every level, the last node that was *kept*.
+//! When a node has to go, those are exactly the nodes whose `fwd` pointers
+//! skip over it, so it can be unlinked from every level without searching
+//! again from the head.
+
+use crate::{NodeRef, SkipList};
+use std::ops::{Bound, RangeBounds};
+use std::sync::Arc;
+
+/// A single left-to-right unlinking pass over part of a list.
+struct Sweep<'a, K, V> {
+    list: &'a mut SkipList<K, V>,
+    /// Last kept node at each level
+    update: Vec<NodeRef<K, V>>,
+    /// Next node to look at (`None` once the pass is over)
+    curr: Option<NodeRef<K, V>>,
+    /// Node the pass ends with (`None` to run to the end of the list)
+    last: Option<NodeRef<K, V>>,
+}
+
+impl<'a, K, V> Sweep<'a, K, V>
+where
+    K: Ord,
+{
+    /// A pass over the whole list
+    fn all(list: &'a mut SkipList<K, V>) -> Self {
+        let update = vec![Arc::clone(&list.head); list.max];
+        let curr = list.head.read().unwrap().fwd[0].clone();
+        Sweep { list, update, curr, last: None }
+    }
+
+    /// A pass over the keys in `range`
+    fn range<R>(list: &'a mut SkipList<K, V>, range: R) -> Self
+    where
+        R: RangeBounds<K>,
+    {
+        let mut sweep = match range.start_bound() {
+            Bound::Included(start) | Bound::Excluded(start) => {
+                let update = list.find_predecessors(start);
+                let curr = update[0].read().unwrap().fwd[0].clone();
+                Sweep { list, update, curr, last: None }
+            }
+            Bound::Unbounded => Sweep::all(list),
+        };
+
+        // An excluded start key is kept, so it becomes a predecessor
+        if let Bound::Excluded(start) = range.start_bound() {
+            if let Some(node) = sweep.curr.clone() {
+                if node.read().unwrap().key.as_ref() == Some(start) {
+                    sweep.keep(&node);
+                    sweep.curr = node.read().unwrap().fwd[0].clone();
+                }
+            }
+        }
+
+        sweep.last = match range.end_bound() {
+            Bound::Included(end) => sweep.list.last_at_most(end, true),
+            Bound::Excluded(end) => sweep.list.last_at_most(end, false),
+            Bound::Unbounded => return sweep,
+        };
+
+        // Nothing to visit when the end lands before the start
+        let empty = match (&sweep.curr, &sweep.last) {
+            (Some(curr), Some(last)) => curr.read().unwrap().key > last.read().unwrap().key,
+            _ => true,
+        };
+        if empty {
+            sweep.curr = None;
+        }
+        sweep
+    }
+
+    /// Record `node` as the last kept node on each of its levels
+    fn keep(&mut self, node: &NodeRef<K, V>) {
+        let height = node.read().unwrap().height();
+        for pred in self.update.iter_mut().take(height) {
+            *pred = Arc::clone(node);
+        }
+    }
+
+    /// Advance to the next node matching `pred`, unlink it and return its entry
+    fn next_matching<F>(&mut self, pred: &mut F) -> Option<(K, V)>
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        loop {
+            let node = self.curr.take()?;
+            if !self.last.as_ref().is_some_and(|last| Arc::ptr_eq(last, &node)) {
+                self.curr = node.read().unwrap().fwd[0].clone();
+            }
+
+            let matched = {
+                let mut node_ref = node.write().unwrap();
+                // `pred` may change the value, and so its weight
+                let before = self.list.bounds.weigh(&node_ref);
+                let matched = {
+                    let node_ref = &mut *node_ref;
+                    match (&node_ref.key, &mut node_ref.val) {
+                        (Some(key), Some(val)) => pred(key, val),
+                        _ => false,
+                    }
+                };
+                self.list.bounds.reweighed(&node_ref, before);
+                matched
+            };
+
+            if matched {
+                return self.list.unlink(&self.update, &node);
+            }
+            self.keep(&node);
+        }
+    }
+}
+
+/// Draining iterator over a [`SkipList`].
+///
+/// Created by [`SkipList::drain`] and [`SkipList::drain_range`]. Entries that
+/// are not consumed are still removed when the iterator is dropped.
+pub struct Drain<'a, K, V>
+where
+    K: Ord,
+{
+    sweep: Sweep<'a, K, V>,
+}
+
+impl<K, V> Iterator for Drain<'_, K, V>
+where
+    K: Ord,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.sweep.next_matching(&mut |_, _| true)
+    }
+}
+
+impl<K, V> Drop for Drain<'_, K, V>
+where
+    K: Ord,
+{
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+/// Iterator that removes the entries matching a predicate.
+///
+/// Created by [`SkipList::extract_if`]. Entries that are not reached before
+/// the iterator is dropped stay in the list.
+pub struct ExtractIf<'a, K, V, F>
+where
+    K: Ord,
+    F: FnMut(&K, &mut V) -> bool,
+{
+    sweep: Sweep<'a, K, V>,
+    pred: F,
+}
+
+impl<K, V, F> Iterator for ExtractIf<'_, K, V, F>
+where
+    K: Ord,
+    F: FnMut(&K, &mut V) -> bool,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.sweep.next_matching(&mut self.pred)
+    }
+}
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord,
+{
+    /// Keeps only the entries for which `f` returns true.
+    ///
+    /// The values of kept entries may be modified in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// for i in 0..10 {
+    ///     skiplist.insert(i, i);
+    /// }
+    ///
+    /// skiplist.retain(|k, _| k % 2 == 0);
+    /// assert_eq!(skiplist.len(), 5);
+    /// assert_eq!(skiplist.get(&3), None);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.extract_if(|k, v| !f(k, v)).for_each(drop);
+    }
+
+    /// Removes every entry, returning them in key order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.insert(2, "two");
+    /// skiplist.insert(1, "one");
+    ///
+    /// let entries: Vec<_> = skiplist.drain().collect();
+    /// assert_eq!(entries, vec![(1, "one"), (2, "two")]);
+    /// assert!(skiplist.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, K, V> {
+        Drain { sweep: Sweep::all(self) }
+    }
+
+    /// Removes the entries whose keys fall in `range`, returning them in key order.
+    ///
+    /// The pass starts with one search for the start of the range and stops at
+    /// its end, so entries outside the range are never visited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// for i in 0..10 {
+    ///     skiplist.insert(i, i * 10);
+    /// }
+    ///
+    /// let drained: Vec<_> = skiplist.drain_range(3..6).map(|(k, _)| k).collect();
+    /// assert_eq!(drained, vec![3, 4, 5]);
+    /// assert_eq!(skiplist.len(), 7);
+    /// ```
+    pub fn drain_range<R>(&mut self, range: R) -> Drain<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        Drain { sweep: Sweep::range(self, range) }
+    }
+
+    /// Returns an iterator that removes and yields the entries for which
+    /// `pred` returns true.
+    ///
+    /// Entries are visited in key order and only as the iterator is advanced;
+    /// dropping it early leaves the rest of the list untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// for i in 0..10 {
+    ///     skiplist.insert(i, i);
+    /// }
+    ///
+    /// let stale: Vec<_> = skiplist.extract_if(|_, v| *v < 3).map(|(k, _)| k).collect();
+    /// assert_eq!(stale, vec![0, 1, 2]);
+    /// assert_eq!(skiplist.len(), 7);
+    /// ```
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, K, V, F>
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        ExtractIf { sweep: Sweep::all(self), pred }
+    }
+}
@@ -0,0 +1,247 @@
+//! Finger search: lookups that start from where the last one ended.
+//!
+//! A [`Finger`] keeps the path of the previous search, the rightmost node
+//! before its key at every level (the `update` vector `insert` builds). The
+//! next search climbs that path only until it finds a level whose node is
+//! still before the new key and whose next link, one level up, doesn't pass
+//! it, then walks down as usual. A key d positions away from the last one
+//! costs O(log d) expected instead of O(log n).
+//!
+//! Fingers never borrow their list, so the list can change between uses.
+//! Nodes inserted since are simply walked past. A node removed since is
+//! treated as unusable and the search climbs above it, falling back to the
+//! head if the whole path is gone. A finger used on another list, or on one
+//! that has been split or appended since, starts over from the head.
+
+use crate::sync::{Arc, RwLock, Weak};
+use crate::{Iter, Node, NodeRef, SkipList};
+use std::ops::{Bound, RangeBounds};
+
+/// A remembered search position in a [`SkipList`].
+///
+/// Created by [`SkipList::finger`] and moved by
+/// [`get_near`](SkipList::get_near), [`insert_near`](SkipList::insert_near)
+/// and [`range_near`](SkipList::range_near).
+///
+/// # Examples
+///
+/// ```
+/// use skiplist_rs::SkipList;
+///
+/// let mut log = SkipList::new();
+/// let mut finger = log.finger();
+/// for t in 0..1000 {
+///     // Each insert lands right after the previous one
+///     log.insert_near(&mut finger, t, t * 2);
+/// }
+/// assert_eq!(log.get_near(&mut finger, &998), Some(1996));
+/// assert_eq!(log.get_near(&mut finger, &5), Some(10));
+/// ```
+pub struct Finger<K, V> {
+    /// Head of the list the path was taken in
+    head: Weak<RwLock<Node<K, V>>>,
+    /// The list's epoch when the path was taken
+    epoch: u64,
+    /// Rightmost node before the last key at every level
+    path: Vec<NodeRef<K, V>>,
+}
+
+impl<K, V> Clone for Finger<K, V> {
+    fn clone(&self) -> Self {
+        Finger { head: Weak::clone(&self.head), epoch: self.epoch, path: self.path.clone() }
+    }
+}
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord,
+{
+    /// Returns a finger at the front of the list.
+    pub fn finger(&self) -> Finger<K, V> {
+        Finger {
+            head: Arc::downgrade(&self.head),
+            epoch: self.epoch,
+            path: vec![Arc::clone(&self.head); self.max],
+        }
+    }
+
+    /// Make `finger` usable on this list, restarting it if it belongs to
+    /// another list or its nodes may have moved
+    fn claim(&self, finger: &mut Finger<K, V>) {
+        let ours = finger.head.upgrade().is_some_and(|head| Arc::ptr_eq(&head, &self.head));
+        if !ours || finger.epoch != self.epoch {
+            *finger = self.finger();
+        }
+    }
+
+    /// `true` if `node` is the head or still in the list
+    fn is_live(&self, node: &NodeRef<K, V>) -> bool {
+        Arc::ptr_eq(node, &self.head) || self.read_node(node).key.is_some()
+    }
+
+    /// `true` if `node` is live and before `key`
+    fn is_before(&self, node: &NodeRef<K, V>, key: &K) -> bool {
+        Arc::ptr_eq(node, &self.head) || self.read_node(node).key.as_ref().is_some_and(|k| k < key)
+    }
+
+    /// `true` if the link from `node` at `level` leads to a key before `key`
+    fn passes_before(&self, node: &NodeRef<K, V>, level: usize, key: &K) -> bool {
+        let next = self.read_node(node).fwd.get(level).cloned().flatten();
+        next.is_some_and(|next| self.is_before(&next, key))
+    }
+
+    /// Move `path` from the predecessors of an earlier key to those of `key`,
+    /// climbing only as high as needed
+    pub(crate) fn finger_to(&self, path: &mut Vec<NodeRef<K, V>>, key: &K) {
+        // Levels added since the path was taken start at the head
+        while path.len() < self.max {
+            path.push(Arc::clone(&self.head));
+        }
+
+        // Climb until the node is before `key` and the level above can't
+        // get any closer to it
+        let mut top = 0;
+        loop {
+            let usable = self.is_before(&path[top], key);
+            let closer_above = top + 1 < path.len()
+                && (!self.is_live(&path[top + 1]) || self.passes_before(&path[top + 1], top + 1, key));
+            if usable && !closer_above {
+                break;
+            }
+            if top + 1 == path.len() {
+                path[top] = Arc::clone(&self.head);
+                break;
+            }
+            top += 1;
+        }
+
+        let mut curr = Arc::clone(&path[top]);
+        for level in (0..=top).rev() {
+            loop {
+                let next = self.read_node(&curr).fwd[level].clone();
+                match next {
+                    Some(next) if self.is_before(&next, key) => curr = next,
+                    _ => break,
+                }
+            }
+            path[level] = Arc::clone(&curr);
+        }
+    }
+
+    /// Node after the predecessors in `path`, if it holds `key`
+    fn node_at(&self, path: &[NodeRef<K, V>], key: &K) -> Option<NodeRef<K, V>> {
+        let next = self.read_node(&path[0]).fwd[0].clone();
+        next.filter(|next| self.read_node(next).key.as_ref() == Some(key))
+    }
+
+    /// Retrieves a value by its key, starting the search from `finger` and
+    /// leaving the finger at `key`.
+    ///
+    /// Returns the same value as [`get`](Self::get).
+    pub fn get_near(&self, finger: &mut Finger<K, V>, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.claim(finger);
+        self.finger_to(&mut finger.path, key);
+        let Some(node) = self.node_at(&finger.path, key) else {
+            record!(self, misses);
+            return None;
+        };
+        record!(self, hits);
+        let node_ref = self.read_node(&node);
+        self.bounds.touch(&node_ref);
+        node_ref.val.clone()
+    }
+
+    /// Inserts a key-value pair, starting the search from `finger` and
+    /// leaving the finger at `key`.
+    ///
+    /// Returns the same value as [`insert`](Self::insert).
+    pub fn insert_near(&mut self, finger: &mut Finger<K, V>, key: K, value: V) -> Option<V> {
+        self.claim(finger);
+        self.finger_to(&mut finger.path, &key);
+        if self.node_at(&finger.path, &key).is_some() {
+            let old = self.insert_after(&finger.path, key, value);
+            self.evict_to_capacity();
+            return old;
+        }
+
+        // Levels the climb didn't reach may be stale if the list changed
+        // since the finger was last used, so take the new tower's
+        // predecessors from level 0 instead
+        let height = self.random_level();
+        let update = Self::predecessors_from(&finger.path[0], height);
+        finger.path[..height].clone_from_slice(&update);
+        self.link_after(&update, key, value);
+        self.evict_to_capacity();
+        None
+    }
+
+    /// Returns an iterator over the entries whose keys fall in `range`,
+    /// finding both ends from `finger` and leaving the finger at the start.
+    ///
+    /// Yields the same entries as [`range`](Self::range).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// for i in 0..1000 {
+    ///     skiplist.insert(i, i);
+    /// }
+    ///
+    /// // Slide a window along the list
+    /// let mut finger = skiplist.finger();
+    /// for start in (0..1000).step_by(100) {
+    ///     let window: Vec<_> = skiplist.range_near(&mut finger, start..start + 3).map(|(k, _)| k).collect();
+    ///     assert_eq!(window, vec![start, start + 1, start + 2]);
+    /// }
+    /// ```
+    pub fn range_near<R>(&self, finger: &mut Finger<K, V>, range: R) -> Iter<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        self.claim(finger);
+        let front = match range.start_bound() {
+            Bound::Included(start) | Bound::Excluded(start) => {
+                self.finger_to(&mut finger.path, start);
+                let next = self.read_node(&finger.path[0]).fwd[0].clone();
+                match next {
+                    Some(next) if matches!(range.start_bound(), Bound::Excluded(_))
+                        && self.read_node(&next).key.as_ref() == Some(start) =>
+                    {
+                        self.read_node(&next).fwd[0].clone()
+                    }
+                    next => next,
+                }
+            }
+            Bound::Unbounded => self.read_node(&self.head).fwd[0].clone(),
+        };
+        let back = match range.end_bound() {
+            Bound::Included(end) | Bound::Excluded(end) => {
+                // Find the end from a copy so the finger stays at the start
+                let mut path = finger.path.clone();
+                self.finger_to(&mut path, end);
+                let included = matches!(range.end_bound(), Bound::Included(_));
+                match self.node_at(&path, end).filter(|_| included) {
+                    Some(node) => Some(node),
+                    None => Some(Arc::clone(&path[0])).filter(|pred| !Arc::ptr_eq(pred, &self.head)),
+                }
+            }
+            Bound::Unbounded => self.tail.as_ref().and_then(Weak::upgrade),
+        };
+
+        // An empty range leaves the ends crossed over
+        let crossed = match (&front, &back) {
+            (Some(front), Some(back)) => self.read_node(front).key > self.read_node(back).key,
+            _ => true,
+        };
+        if crossed {
+            return Iter::new(None, None);
+        }
+        Iter::new(front, back)
+    }
+}
@@ -0,0 +1,588 @@
+//! Text import and export: CSV and JSON lines.
+//!
+//! Both formats hold one entry per record, a key and a string value:
+//!
+//! ```text
+//! key,value                      {"key":1,"value":"one"}
+//! 1,one                          {"key":2,"value":"two, three"}
+//! 2,"two, three"
+//! ```
+//!
+//! CSV follows RFC 4180: fields containing commas, quotes or line breaks are
+//! quoted, quotes inside them are doubled, and a quoted field may span lines.
+//! In JSON lines every line is an object with exactly the fields `key` and
+//! `value`; a value given as a number or boolean is kept as its text.
+//!
+//! How a key is spelled is up to its [`TextKey`] implementation: integers in
+//! decimal, strings as they are, and byte vectors in hex.
+//!
+//! [`SkipList::import`] and [`SkipList::export`] work one record at a time,
+//! so a file is never held in memory alongside the list built from it.
+
+use crate::SkipList;
+use std::error::Error;
+use std::fmt::{self, Write as _};
+use std::io::{self, BufRead, Write};
+use std::mem;
+
+/// A text format for entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Comma-separated `key,value` records
+    #[default]
+    Csv,
+    /// One `{"key":..,"value":..}` object per line
+    JsonLines,
+}
+
+/// What [`SkipList::import`] does with a key that is already in the list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicatePolicy {
+    /// Replace the existing value, so the last record wins
+    #[default]
+    Overwrite,
+    /// Keep the existing value, so the first record wins
+    KeepFirst,
+    /// Stop the import with [`ImportErrorKind::Duplicate`]
+    Reject,
+}
+
+/// Options for [`SkipList::import`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportOptions {
+    /// Format of the input
+    pub format: Format,
+    /// How to handle a key that is already present
+    pub duplicates: DuplicatePolicy,
+    /// Whether the first CSV record is a header to skip (ignored for JSON lines)
+    pub header: bool,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        ImportOptions {
+            format: Format::Csv,
+            duplicates: DuplicatePolicy::Overwrite,
+            header: true,
+        }
+    }
+}
+
+/// Options for [`SkipList::export`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportOptions {
+    /// Format of the output
+    pub format: Format,
+    /// Whether to start CSV output with a `key,value` header (ignored for JSON lines)
+    pub header: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions { format: Format::Csv, header: true }
+    }
+}
+
+/// Counts of what [`SkipList::import`] did with each record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ImportSummary {
+    /// Records whose key was new
+    pub inserted: u64,
+    /// Records that replaced an existing value
+    pub overwritten: u64,
+    /// Records dropped because their key was already present
+    pub skipped: u64,
+}
+
+impl ImportSummary {
+    /// Total records read.
+    pub fn records(&self) -> u64 {
+        self.inserted + self.overwritten + self.skipped
+    }
+}
+
+/// Error returned by [`SkipList::import`], with the line the bad record
+/// starts on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportError {
+    /// 1-based line number
+    pub line: u64,
+    /// What went wrong
+    pub kind: ImportErrorKind,
+}
+
+/// The reason an import stopped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportErrorKind {
+    /// Reading the input failed
+    Io(io::ErrorKind),
+    /// The record is not valid CSV or JSON, or does not have a key and a value
+    Malformed(String),
+    /// The key field could not be parsed as the list's key type
+    InvalidKey(String),
+    /// The key was already present under [`DuplicatePolicy::Reject`]
+    Duplicate(String),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            ImportErrorKind::Io(kind) => write!(f, "failed to read input: {}", kind),
+            ImportErrorKind::Malformed(reason) => write!(f, "{}", reason),
+            ImportErrorKind::InvalidKey(reason) => write!(f, "invalid key: {}", reason),
+            ImportErrorKind::Duplicate(key) => write!(f, "duplicate key `{}`", key),
+        }
+    }
+}
+
+impl Error for ImportError {}
+
+/// A key type that can be read from and written to a text record.
+///
+/// Implemented for the integer types (decimal), `String` (as is) and
+/// `Vec<u8>` (hex).
+pub trait TextKey: Sized {
+    /// Whether JSON lines write the key as a bare number rather than a string.
+    const JSON_NUMBER: bool = false;
+
+    /// Parses a key from the text of a record's key field.
+    fn parse_text(text: &str) -> Result<Self, String>;
+
+    /// Appends the key's text to `out`.
+    fn write_text(&self, out: &mut String);
+}
+
+macro_rules! text_int {
+    ($($ty:ty),*) => {
+        $(
+            impl TextKey for $ty {
+                const JSON_NUMBER: bool = true;
+
+                fn parse_text(text: &str) -> Result<Self, String> {
+                    text.parse().map_err(|_| format!("`{}` is not a valid {}", text, stringify!($ty)))
+                }
+
+                fn write_text(&self, out: &mut String) {
+                    write!(out, "{}", self).unwrap();
+                }
+            }
+        )*
+    };
+}
+
+text_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl TextKey for String {
+    fn parse_text(text: &str) -> Result<Self, String> {
+        Ok(text.to_string())
+    }
+
+    fn write_text(&self, out: &mut String) {
+        out.push_str(self);
+    }
+}
+
+impl TextKey for Vec<u8> {
+    fn parse_text(text: &str) -> Result<Self, String> {
+        let invalid = || format!("`{}` is not valid hex", text);
+        if !text.len().is_multiple_of(2) {
+            return Err(invalid());
+        }
+        (0..text.len())
+            .step_by(2)
+            .map(|i| text.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()).ok_or_else(invalid))
+            .collect()
+    }
+
+    fn write_text(&self, out: &mut String) {
+        for byte in self {
+            write!(out, "{:02x}", byte).unwrap();
+        }
+    }
+}
+
+/// Reads the input line by line, tracking line numbers
+struct Lines<R> {
+    reader: R,
+    line: u64,
+    buf: String,
+}
+
+impl<R: BufRead> Lines<R> {
+    /// Read the next line into `buf`; false at end of input
+    fn advance(&mut self) -> Result<bool, ImportError> {
+        self.buf.clear();
+        let read = self.reader.read_line(&mut self.buf).map_err(|err| {
+            let kind = match err.kind() {
+                io::ErrorKind::InvalidData => ImportErrorKind::Malformed("input is not valid UTF-8".to_string()),
+                kind => ImportErrorKind::Io(kind),
+            };
+            ImportError { line: self.line + 1, kind }
+        })?;
+        if read == 0 {
+            return Ok(false);
+        }
+        self.line += 1;
+        Ok(true)
+    }
+
+    /// Skip blank lines; false at end of input
+    fn advance_to_record(&mut self) -> Result<bool, ImportError> {
+        while self.advance()? {
+            if !self.buf.trim().is_empty() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Read the next CSV record, which may continue over several lines inside
+    /// a quoted field. Returns the line it starts on and its fields.
+    fn csv_record(&mut self) -> Result<Option<(u64, Vec<String>)>, ImportError> {
+        if !self.advance_to_record()? {
+            return Ok(None);
+        }
+        let start = self.line;
+        let malformed = |reason: &str| ImportError { line: start, kind: ImportErrorKind::Malformed(reason.to_string()) };
+
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        // Inside a quoted field, and whether the current field was quoted at all
+        let mut in_quotes = false;
+        let mut quoted = false;
+        loop {
+            let content = self.buf.trim_end_matches('\n').trim_end_matches('\r');
+            let mut chars = content.chars().peekable();
+            while let Some(c) = chars.next() {
+                if in_quotes {
+                    if c != '"' {
+                        field.push(c);
+                    } else if chars.peek() == Some(&'"') {
+                        chars.next();
+                        field.push('"');
+                    } else {
+                        in_quotes = false;
+                    }
+                    continue;
+                }
+                match c {
+                    ',' => {
+                        fields.push(mem::take(&mut field));
+                        quoted = false;
+                    }
+                    '"' if field.is_empty() && !quoted => {
+                        in_quotes = true;
+                        quoted = true;
+                    }
+                    '"' => return Err(malformed("quote inside an unquoted field")),
+                    _ if quoted => return Err(malformed("text after a closing quote")),
+                    c => field.push(c),
+                }
+            }
+            if !in_quotes {
+                break;
+            }
+            // The line break is part of the quoted field
+            field.push_str(&self.buf[content.len()..]);
+            if !self.advance()? {
+                return Err(malformed("unterminated quoted field"));
+            }
+        }
+        fields.push(field);
+        Ok(Some((start, fields)))
+    }
+}
+
+/// Append `field` to `out`, quoting it if CSV requires
+fn write_csv_field(out: &mut String, field: &str) {
+    if field.contains([',', '"', '\n', '\r']) {
+        out.push('"');
+        out.push_str(&field.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(field);
+    }
+}
+
+/// Append `text` to `out` as a JSON string
+fn write_json_string(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Just enough of a JSON parser for one `{"key":..,"value":..}` line
+struct Json<'a> {
+    rest: &'a str,
+}
+
+impl Json<'_> {
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start_matches([' ', '\t', '\n', '\r']);
+    }
+
+    /// Consume `c` after any whitespace, if it is next
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        match self.rest.strip_prefix(c) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(format!("expected `{}`", c))
+        }
+    }
+
+    /// Parse the line's object into its key and value texts
+    fn entry(&mut self) -> Result<(String, String), String> {
+        let (mut key, mut value) = (None, None);
+        self.expect('{')?;
+        if !self.eat('}') {
+            loop {
+                self.expect('"')?;
+                let name = self.string()?;
+                self.expect(':')?;
+                let slot = match name.as_str() {
+                    "key" => &mut key,
+                    "value" => &mut value,
+                    other => return Err(format!("unexpected field `{}`", other)),
+                };
+                if slot.replace(self.scalar()?).is_some() {
+                    return Err(format!("field `{}` appears twice", name));
+                }
+                if self.eat('}') {
+                    break;
+                }
+                self.expect(',')?;
+            }
+        }
+        self.skip_whitespace();
+        if !self.rest.is_empty() {
+            return Err("unexpected text after the object".to_string());
+        }
+        Ok((key.ok_or("missing field `key`")?, value.ok_or("missing field `value`")?))
+    }
+
+    /// A string, or the text of a number or boolean
+    fn scalar(&mut self) -> Result<String, String> {
+        if self.eat('"') {
+            return self.string();
+        }
+        let end = self.rest.find([',', '}', ' ', '\t', '\r', '\n']).unwrap_or(self.rest.len());
+        let (text, rest) = self.rest.split_at(end);
+        let number = text.starts_with(['-', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9'])
+            && text.parse::<f64>().is_ok();
+        if !(number || text == "true" || text == "false") {
+            return Err(format!("expected a string, number or boolean, found `{}`", text));
+        }
+        self.rest = rest;
+        Ok(text.to_string())
+    }
+
+    /// The rest of a string whose opening quote was consumed
+    fn string(&mut self) -> Result<String, String> {
+        let mut out = String::new();
+        let mut chars = self.rest.chars();
+        loop {
+            match chars.next().ok_or("unterminated string")? {
+                '"' => break,
+                '\\' => {
+                    let c = match chars.next().ok_or("unterminated string")? {
+                        '"' => '"',
+                        '\\' => '\\',
+                        '/' => '/',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => {
+                            let high = hex4(&mut chars)?;
+                            let code = if (0xD800..0xDC00).contains(&high) {
+                                if chars.next() != Some('\\') || chars.next() != Some('u') {
+                                    return Err("unpaired surrogate in `\\u` escape".to_string());
+                                }
+                                let low = hex4(&mut chars)?;
+                                if !(0xDC00..0xE000).contains(&low) {
+                                    return Err("unpaired surrogate in `\\u` escape".to_string());
+                                }
+                                0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                            } else {
+                                high
+                            };
+                            char::from_u32(code).ok_or("invalid `\\u` escape")?
+                        }
+                        other => return Err(format!("invalid escape `\\{}`", other)),
+                    };
+                    out.push(c);
+                }
+                c if (c as u32) < 0x20 => return Err("unescaped control character in string".to_string()),
+                c => out.push(c),
+            }
+        }
+        self.rest = chars.as_str();
+        Ok(out)
+    }
+}
+
+/// Four hex digits of a `\u` escape
+fn hex4(chars: &mut std::str::Chars<'_>) -> Result<u32, String> {
+    let digits: String = chars.by_ref().take(4).collect();
+    match u32::from_str_radix(&digits, 16) {
+        Ok(code) if digits.len() == 4 => Ok(code),
+        _ => Err("invalid `\\u` escape".to_string()),
+    }
+}
+
+impl<K> SkipList<K, String>
+where
+    K: Ord + Clone + TextKey,
+{
+    /// Inserts every record read from `reader`, returning what was done with
+    /// them.
+    ///
+    /// Records are parsed and inserted one at a time, so memory use is the
+    /// list plus a single record. On error, the records before the bad one
+    /// stay in the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::{DuplicatePolicy, ImportOptions, SkipList};
+    ///
+    /// let csv = "key,value\n2,two\n1,one\n2,deux\n";
+    ///
+    /// let mut skiplist: SkipList<i64, String> = SkipList::new();
+    /// let options = ImportOptions { duplicates: DuplicatePolicy::KeepFirst, ..Default::default() };
+    /// let summary = skiplist.import(csv.as_bytes(), &options).unwrap();
+    ///
+    /// assert_eq!((summary.inserted, summary.skipped), (2, 1));
+    /// assert_eq!(skiplist.get(&2), Some("two".to_string()));
+    /// ```
+    pub fn import<R: BufRead>(&mut self, reader: R, options: &ImportOptions) -> Result<ImportSummary, ImportError> {
+        let mut lines = Lines { reader, line: 0, buf: String::new() };
+        let mut summary = ImportSummary::default();
+        let mut skip_header = options.header && options.format == Format::Csv;
+
+        loop {
+            let (line, key, value) = match options.format {
+                Format::Csv => {
+                    let Some((line, fields)) = lines.csv_record()? else { break };
+                    if mem::take(&mut skip_header) {
+                        continue;
+                    }
+                    let Ok::<[String; 2], _>([key, value]) = fields.try_into() else {
+                        let reason = "expected 2 fields, `key,value`".to_string();
+                        return Err(ImportError { line, kind: ImportErrorKind::Malformed(reason) });
+                    };
+                    (line, key, value)
+                }
+                Format::JsonLines => {
+                    if !lines.advance_to_record()? {
+                        break;
+                    }
+                    let (key, value) = Json { rest: &lines.buf }
+                        .entry()
+                        .map_err(|reason| ImportError { line: lines.line, kind: ImportErrorKind::Malformed(reason) })?;
+                    (lines.line, key, value)
+                }
+            };
+
+            let parsed = K::parse_text(&key).map_err(|reason| ImportError { line, kind: ImportErrorKind::InvalidKey(reason) })?;
+            match options.duplicates {
+                DuplicatePolicy::Overwrite => match self.insert(parsed, value) {
+                    Some(_) => summary.overwritten += 1,
+                    None => summary.inserted += 1,
+                },
+                _ if !self.contains_key(&parsed) => {
+                    self.insert(parsed, value);
+                    summary.inserted += 1;
+                }
+                DuplicatePolicy::KeepFirst => summary.skipped += 1,
+                DuplicatePolicy::Reject => {
+                    return Err(ImportError { line, kind: ImportErrorKind::Duplicate(key) });
+                }
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Writes every entry to `writer` in key order, returning how many were
+    /// written.
+    ///
+    /// Entries are formatted and written one at a time. The writer is not
+    /// buffered here; wrap files in a `BufWriter`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::{ExportOptions, Format, SkipList};
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.insert(2, "two, three".to_string());
+    /// skiplist.insert(1, "one".to_string());
+    ///
+    /// let mut csv = Vec::new();
+    /// skiplist.export(&mut csv, &ExportOptions::default()).unwrap();
+    /// assert_eq!(csv, b"key,value\n1,one\n2,\"two, three\"\n");
+    ///
+    /// let mut jsonl = Vec::new();
+    /// let options = ExportOptions { format: Format::JsonLines, ..Default::default() };
+    /// skiplist.export(&mut jsonl, &options).unwrap();
+    /// assert_eq!(jsonl, b"{\"key\":1,\"value\":\"one\"}\n{\"key\":2,\"value\":\"two, three\"}\n");
+    /// ```
+    pub fn export<W: Write>(&self, mut writer: W, options: &ExportOptions) -> io::Result<u64> {
+        if options.header && options.format == Format::Csv {
+            writer.write_all(b"key,value\n")?;
+        }
+        let mut record = String::new();
+        let mut key_text = String::new();
+        let mut written = 0;
+        for (key, value) in self.iter() {
+            record.clear();
+            key_text.clear();
+            key.write_text(&mut key_text);
+            match options.format {
+                Format::Csv => {
+                    write_csv_field(&mut record, &key_text);
+                    record.push(',');
+                    write_csv_field(&mut record, &value);
+                }
+                Format::JsonLines => {
+                    record.push_str("{\"key\":");
+                    if K::JSON_NUMBER {
+                        record.push_str(&key_text);
+                    } else {
+                        write_json_string(&mut record, &key_text);
+                    }
+                    record.push_str(",\"value\":");
+                    write_json_string(&mut record, &value);
+                    record.push('}');
+                }
+            }
+            record.push('\n');
+            writer.write_all(record.as_bytes())?;
+            written += 1;
+        }
+        Ok(written)
+    }
+}
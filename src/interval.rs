@@ -0,0 +1,478 @@
+//! Interval skip list (Hanson, 1991) for stabbing and overlap queries.
+//!
+//! Every interval endpoint is a node in a skip list. An interval `[a, b]` is
+//! then recorded as *markers* on the fewest edges that exactly tile it: the
+//! placement climbs from `a` using the tallest edges that stay inside the
+//! interval and descends again towards `b`, so an interval is marked on
+//! O(log n) edges. Each node also keeps the intervals marked on the edges
+//! that touch it, for queries that land exactly on an endpoint.
+//!
+//! ```text
+//! Level 1: [H] -----------> [2] ======={I}======> [7] -------> end
+//! Level 0: [H] --> [1] --> [2] --> [5] --> [7] -{I}-> [9] --> end
+//!
+//! I = [2, 9] is marked on 2 -> 7 at level 1 and 7 -> 9 at level 0
+//! ```
+//!
+//! A stabbing query for `x` is an ordinary search for `x`: the edges it
+//! crosses that strictly contain `x` are exactly the marked edges that could
+//! hold an interval containing `x`, so it costs O(log n + k) for k results.
+//!
+//! Inserting or removing an endpoint splits or merges the edges on its
+//! search path; only the intervals marked on those edges are re-placed.
+//!
+//! The endpoints don't sit in a [`SkipList`](crate::SkipList) because the
+//! markers hang off individual edges: placing or re-placing an interval
+//! reads and writes the edges of whole towers at once, and intervals have to
+//! name the edges they are marked on so removal can find them again. With
+//! `Arc<RwLock<Node>>` links that would mean holding many node locks at a
+//! time and storing `Arc`s to edges that splits and merges keep replacing.
+//! So, like [`AugmentedSkipList`](crate::AugmentedSkipList), nodes live in an
+//! arena and link by index, and an edge is simply `(node, level)`. Tower
+//! heights and the number of levels still come from the same helpers as the
+//! other lists.
+
+use crate::level;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::ops::RangeInclusive;
+
+/// Index of the head in the arena
+const HEAD: usize = 0;
+
+/// Handle to an interval in an [`IntervalSkipList`], returned by
+/// [`insert`](IntervalSkipList::insert).
+///
+/// Handles are never reused, so a handle to a removed interval stays dead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IntervalId {
+    index: usize,
+    generation: u64,
+}
+
+/// An endpoint node
+struct Node<K> {
+    /// `None` for the head and for free slots
+    key: Option<K>,
+    links: Vec<Option<usize>>,
+    /// Intervals marked on each outgoing edge
+    markers: Vec<Vec<usize>>,
+    /// Intervals marked on an edge into or out of this node
+    eq_markers: Vec<usize>,
+    /// Intervals that start here
+    starts: Vec<usize>,
+    /// Interval endpoints sharing this node
+    owners: usize,
+}
+
+struct Interval<K, V> {
+    range: RangeInclusive<K>,
+    value: V,
+    /// `(node, level)` of every edge marked with this interval
+    marks: Vec<(usize, usize)>,
+    /// Nodes whose `eq_markers` hold this interval
+    eq_nodes: Vec<usize>,
+}
+
+struct Slot<K, V> {
+    generation: u64,
+    interval: Option<Interval<K, V>>,
+}
+
+/// A collection of closed intervals `[start, end]` with values, answering
+/// "which intervals contain `x`" and "which intervals overlap `[a, b]`".
+///
+/// The same interval may be inserted more than once; each insert gets its
+/// own [`IntervalId`].
+///
+/// # Examples
+///
+/// ```
+/// use skiplist_rs::IntervalSkipList;
+///
+/// let mut meetings = IntervalSkipList::new();
+/// meetings.insert(9..=10, "standup");
+/// meetings.insert(10..=12, "review");
+/// meetings.insert(14..=15, "1:1");
+///
+/// let at_ten: Vec<_> = meetings.stab(&10).into_iter().map(|(_, _, name)| *name).collect();
+/// assert_eq!(at_ten, vec!["standup", "review"]);
+///
+/// let afternoon: Vec<_> = meetings.overlapping(&12, &18).into_iter().map(|(_, _, name)| *name).collect();
+/// assert_eq!(afternoon, vec!["review", "1:1"]);
+/// ```
+pub struct IntervalSkipList<K, V> {
+    nodes: Vec<Node<K>>,
+    /// Arena slots of removed nodes
+    free_nodes: Vec<usize>,
+    intervals: Vec<Slot<K, V>>,
+    /// Interval slots that can be reused
+    free_intervals: Vec<usize>,
+    max: usize,
+    len: usize,
+    p: f64,
+    /// Set by [`seed`](Self::seed) to make endpoint towers repeatable
+    rng: Option<StdRng>,
+}
+
+impl<K, V> Default for IntervalSkipList<K, V>
+where
+    K: Ord + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A query result: the interval's handle, bounds and value
+pub type IntervalEntry<'a, K, V> = (IntervalId, &'a RangeInclusive<K>, &'a V);
+
+impl<K, V> IntervalSkipList<K, V>
+where
+    K: Ord + Clone,
+{
+    /// Creates an empty list with probability 0.5 and 4 initial levels.
+    pub fn new() -> Self {
+        Self::with_params(4, 0.5)
+    }
+
+    /// Creates an empty list whose endpoint towers start with `initial_max`
+    /// levels and grow with probability `p`.
+    pub fn with_params(initial_max: usize, p: f64) -> Self {
+        let max = initial_max.max(1);
+        IntervalSkipList {
+            nodes: vec![Self::node(None, max)],
+            free_nodes: Vec::new(),
+            intervals: Vec::new(),
+            free_intervals: Vec::new(),
+            max,
+            len: 0,
+            p,
+            rng: None,
+        }
+    }
+
+    /// Seeds the endpoint tower heights, so the same insertions always build
+    /// the same marker placement.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = Some(StdRng::seed_from_u64(seed));
+    }
+
+    /// Returns the number of intervals.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the list holds no intervals.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn node(key: Option<K>, height: usize) -> Node<K> {
+        Node {
+            key,
+            links: vec![None; height],
+            markers: vec![Vec::new(); height],
+            eq_markers: Vec::new(),
+            starts: Vec::new(),
+            owners: 0,
+        }
+    }
+
+    fn key(&self, node: usize) -> &K {
+        self.nodes[node].key.as_ref().expect("linked node has a key")
+    }
+
+    fn next(&self, node: usize, level: usize) -> Option<usize> {
+        self.nodes[node].links[level]
+    }
+
+    fn interval(&self, index: usize) -> &Interval<K, V> {
+        self.intervals[index].interval.as_ref().expect("marker refers to a live interval")
+    }
+
+    /// Rightmost node before `key` at every level
+    fn predecessors(&self, key: &K) -> Vec<usize> {
+        let mut update = vec![HEAD; self.max];
+        let mut curr = HEAD;
+        for level in (0..self.max).rev() {
+            while let Some(next) = self.next(curr, level).filter(|&next| self.key(next) < key) {
+                curr = next;
+            }
+            update[level] = curr;
+        }
+        update
+    }
+
+    /// The node holding `key`, if it is an endpoint
+    fn find(&self, key: &K) -> Option<usize> {
+        let pred = self.predecessors(key)[0];
+        self.next(pred, 0).filter(|&next| self.key(next) == key)
+    }
+
+    /// Add head levels so towers can grow about log2(endpoints) tall
+    fn resize(&mut self) {
+        let endpoints = self.nodes.len() - self.free_nodes.len();
+        let optimal = level::optimal_levels(endpoints);
+        while self.max < optimal {
+            self.nodes[HEAD].links.push(None);
+            self.nodes[HEAD].markers.push(Vec::new());
+            self.max += 1;
+        }
+    }
+
+    /// Edges and nodes to mark for `range`, whose endpoints must be nodes
+    fn marks_for(&self, range: &RangeInclusive<K>) -> (Vec<(usize, usize)>, Vec<usize>) {
+        let within = |to: Option<usize>| to.is_some_and(|to| self.key(to) <= range.end());
+        let mut x = self.find(range.start()).expect("interval start is an endpoint");
+        let mut marks = Vec::new();
+        let mut eq_nodes = vec![x];
+
+        // Climb while a taller edge still fits inside the interval
+        let mut level = 0;
+        while within(self.next(x, level)) {
+            while level + 1 < self.nodes[x].links.len() && within(self.next(x, level + 1)) {
+                level += 1;
+            }
+            marks.push((x, level));
+            x = self.next(x, level).expect("edge fits inside the interval");
+            eq_nodes.push(x);
+        }
+
+        // Then come down towards the end
+        while self.key(x) != range.end() {
+            while !within(self.next(x, level)) {
+                level -= 1;
+            }
+            marks.push((x, level));
+            x = self.next(x, level).expect("edge fits inside the interval");
+            eq_nodes.push(x);
+        }
+        (marks, eq_nodes)
+    }
+
+    /// Record interval `index` on the edges and nodes that tile it
+    fn place(&mut self, index: usize) {
+        let (marks, eq_nodes) = self.marks_for(&self.interval(index).range);
+        for &(node, level) in &marks {
+            self.nodes[node].markers[level].push(index);
+        }
+        for &node in &eq_nodes {
+            self.nodes[node].eq_markers.push(index);
+        }
+        let interval = self.intervals[index].interval.as_mut().expect("placing a live interval");
+        interval.marks = marks;
+        interval.eq_nodes = eq_nodes;
+    }
+
+    /// Erase every marker of interval `index`
+    fn unplace(&mut self, index: usize) {
+        let interval = self.intervals[index].interval.as_mut().expect("unplacing a live interval");
+        let marks = std::mem::take(&mut interval.marks);
+        let eq_nodes = std::mem::take(&mut interval.eq_nodes);
+        for (node, level) in marks {
+            remove_item(&mut self.nodes[node].markers[level], index);
+        }
+        for node in eq_nodes {
+            remove_item(&mut self.nodes[node].eq_markers, index);
+        }
+    }
+
+    /// Take a reference to the endpoint node for `key`, creating it if needed
+    fn acquire(&mut self, key: &K) -> usize {
+        let update = self.predecessors(key);
+        if let Some(node) = self.next(update[0], 0).filter(|&next| self.key(next) == key) {
+            self.nodes[node].owners += 1;
+            return node;
+        }
+
+        // The new node splits the edges below its height, so the intervals
+        // marked on them must be placed again afterwards
+        let height = level::level_from(&mut self.rng, self.p, self.max);
+        let mut affected = Vec::new();
+        for (level, &pred) in update.iter().enumerate().take(height) {
+            affected.extend_from_slice(&self.nodes[pred].markers[level]);
+        }
+        affected.sort_unstable();
+        affected.dedup();
+        for &index in &affected {
+            self.unplace(index);
+        }
+
+        let mut node = Self::node(Some(key.clone()), height);
+        node.owners = 1;
+        let index = match self.free_nodes.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        for (level, &pred) in update.iter().enumerate().take(height) {
+            self.nodes[index].links[level] = self.nodes[pred].links[level].replace(index);
+        }
+
+        for index in affected {
+            self.place(index);
+        }
+        self.resize();
+        index
+    }
+
+    /// Drop a reference to endpoint `node`, removing it once unused
+    fn release(&mut self, node: usize) {
+        self.nodes[node].owners -= 1;
+        if self.nodes[node].owners > 0 {
+            return;
+        }
+
+        // Removing the node merges the edges on either side of it
+        let update = self.predecessors(self.key(node));
+        let height = self.nodes[node].links.len();
+        let mut affected = self.nodes[node].eq_markers.clone();
+        for (level, &pred) in update.iter().enumerate().take(height) {
+            affected.extend_from_slice(&self.nodes[pred].markers[level]);
+            affected.extend_from_slice(&self.nodes[node].markers[level]);
+        }
+        affected.sort_unstable();
+        affected.dedup();
+        for &index in &affected {
+            self.unplace(index);
+        }
+
+        for (level, &pred) in update.iter().enumerate().take(height) {
+            self.nodes[pred].links[level] = self.nodes[node].links[level];
+        }
+        self.nodes[node] = Self::node(None, 0);
+        self.free_nodes.push(node);
+
+        for index in affected {
+            self.place(index);
+        }
+    }
+
+    /// Adds the closed interval `range` with `value`, returning its handle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interval's start is greater than its end.
+    pub fn insert(&mut self, range: RangeInclusive<K>, value: V) -> IntervalId {
+        assert!(range.start() <= range.end(), "interval start is greater than its end");
+
+        let start = self.acquire(range.start());
+        self.acquire(range.end());
+
+        let interval = Interval { range, value, marks: Vec::new(), eq_nodes: Vec::new() };
+        let index = match self.free_intervals.pop() {
+            Some(index) => {
+                self.intervals[index].interval = Some(interval);
+                index
+            }
+            None => {
+                self.intervals.push(Slot { generation: 0, interval: Some(interval) });
+                self.intervals.len() - 1
+            }
+        };
+        self.nodes[start].starts.push(index);
+        self.place(index);
+        self.len += 1;
+        IntervalId { index, generation: self.intervals[index].generation }
+    }
+
+    /// Returns the bounds and value of the interval behind `id`.
+    pub fn get(&self, id: IntervalId) -> Option<(&RangeInclusive<K>, &V)> {
+        let slot = self.intervals.get(id.index).filter(|slot| slot.generation == id.generation)?;
+        slot.interval.as_ref().map(|interval| (&interval.range, &interval.value))
+    }
+
+    /// Removes the interval behind `id`, returning its bounds and value.
+    pub fn remove(&mut self, id: IntervalId) -> Option<(RangeInclusive<K>, V)> {
+        self.get(id)?;
+        self.unplace(id.index);
+
+        let slot = &mut self.intervals[id.index];
+        let interval = slot.interval.take().expect("checked above");
+        slot.generation += 1;
+        self.free_intervals.push(id.index);
+
+        let start = self.find(interval.range.start()).expect("interval start is an endpoint");
+        let end = self.find(interval.range.end()).expect("interval end is an endpoint");
+        remove_item(&mut self.nodes[start].starts, id.index);
+        self.release(start);
+        self.release(end);
+        self.len -= 1;
+        Some((interval.range, interval.value))
+    }
+
+    /// Returns every interval containing `point`, ordered by start, then end.
+    ///
+    /// Runs in O(log n + k) expected time for k results, plus sorting them.
+    pub fn stab(&self, point: &K) -> Vec<IntervalEntry<'_, K, V>> {
+        let mut found = self.stab_indices(point);
+        self.entries(&mut found)
+    }
+
+    /// Returns every interval overlapping the closed range `[start, end]`,
+    /// ordered by start, then end.
+    ///
+    /// That is the intervals containing `start` plus those starting inside
+    /// `(start, end]`; each endpoint visited yields at least one result, so
+    /// this is O(log n + k) expected for k results, plus sorting them.
+    pub fn overlapping(&self, start: &K, end: &K) -> Vec<IntervalEntry<'_, K, V>> {
+        if start > end {
+            return Vec::new();
+        }
+        let mut found = self.stab_indices(start);
+        let mut next = self.next(self.predecessors(start)[0], 0);
+        while let Some(node) = next.filter(|&node| self.key(node) <= end) {
+            if self.key(node) > start {
+                found.extend_from_slice(&self.nodes[node].starts);
+            }
+            next = self.next(node, 0);
+        }
+        self.entries(&mut found)
+    }
+
+    /// Indices of the intervals containing `point`
+    fn stab_indices(&self, point: &K) -> Vec<usize> {
+        let mut found = Vec::new();
+        let mut curr = HEAD;
+        for level in (0..self.max).rev() {
+            while let Some(next) = self.next(curr, level).filter(|&next| self.key(next) < point) {
+                curr = next;
+            }
+            // An edge ending exactly on `point` is covered by the node's own markers
+            if self.next(curr, level).is_none_or(|next| self.key(next) != point) {
+                found.extend_from_slice(&self.nodes[curr].markers[level]);
+            }
+        }
+        if let Some(node) = self.next(curr, 0).filter(|&next| self.key(next) == point) {
+            found.extend_from_slice(&self.nodes[node].eq_markers);
+        }
+        found
+    }
+
+    /// Turn interval indices into sorted query results
+    fn entries(&self, found: &mut [usize]) -> Vec<IntervalEntry<'_, K, V>> {
+        let mut entries: Vec<_> = found
+            .iter()
+            .map(|&index| {
+                let interval = self.interval(index);
+                let id = IntervalId { index, generation: self.intervals[index].generation };
+                (id, &interval.range, &interval.value)
+            })
+            .collect();
+        entries.sort_by(|a, b| (a.1.start(), a.1.end(), a.0).cmp(&(b.1.start(), b.1.end(), b.0)));
+        entries
+    }
+}
+
+/// Remove one occurrence of `item` from an unordered list
+fn remove_item(items: &mut Vec<usize>, item: usize) {
+    if let Some(pos) = items.iter().position(|&i| i == item) {
+        items.swap_remove(pos);
+    }
+}
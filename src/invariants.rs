@@ -0,0 +1,347 @@
+//! Structural invariant checks for debugging and tests.
+//!
+//! [`SkipList::check_invariants`] walks every level of a list and verifies
+//! the properties the search and update code relies on. Violations are
+//! reported by position (index along the level being walked) so that keys
+//! don't need to implement `Debug`.
+
+use crate::{NodeRef, SkipList};
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Weak};
+
+/// A broken skip list invariant, returned by [`SkipList::check_invariants`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvariantError {
+    /// The head has a different number of levels than the list's `max`
+    HeadLevels { head: usize, max: usize },
+    /// A node without a key or value is linked in after the head
+    MissingEntry { index: usize },
+    /// A key is smaller than the key before it at level 0
+    Unsorted { index: usize },
+    /// A key is equal to the key before it at level 0
+    DuplicateKey { index: usize },
+    /// A node is linked into a level its tower does not reach
+    LinkAboveTower { level: usize, index: usize },
+    /// A node's tower is taller than the list's `max`
+    TowerTooTall { index: usize, height: usize, max: usize },
+    /// A node at `level` does not appear, in order, at the level below
+    NotSubsequence { level: usize, index: usize },
+    /// A tower tall enough for `level` is skipped there; `index` is where it
+    /// belongs along `level`
+    SkippedTower { level: usize, index: usize },
+    /// A node's level-0 back link does not point at its predecessor
+    BrokenBackLink { index: usize },
+    /// The tail pointer is not the last node at level 0
+    TailMismatch,
+    /// `len` does not match the number of nodes at level 0
+    LengthMismatch { len: usize, counted: usize },
+}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvariantError::HeadLevels { head, max } => {
+                write!(f, "head has {} levels but max is {}", head, max)
+            }
+            InvariantError::MissingEntry { index } => {
+                write!(f, "node {} at level 0 has no key or value", index)
+            }
+            InvariantError::Unsorted { index } => {
+                write!(f, "node {} at level 0 is smaller than its predecessor", index)
+            }
+            InvariantError::DuplicateKey { index } => {
+                write!(f, "node {} at level 0 repeats its predecessor's key", index)
+            }
+            InvariantError::LinkAboveTower { level, index } => {
+                write!(f, "node {} at level {} has a tower that ends below that level", index, level)
+            }
+            InvariantError::TowerTooTall { index, height, max } => {
+                write!(f, "node {} has height {} but max is {}", index, height, max)
+            }
+            InvariantError::NotSubsequence { level, index } => {
+                write!(f, "node {} at level {} is missing from level {}", index, level, level - 1)
+            }
+            InvariantError::SkippedTower { level, index } => {
+                write!(f, "a tower reaching level {} is missing from it before node {}", level, index)
+            }
+            InvariantError::BrokenBackLink { index } => {
+                write!(f, "node {} at level 0 has a back link to the wrong node", index)
+            }
+            InvariantError::TailMismatch => write!(f, "tail is not the last node at level 0"),
+            InvariantError::LengthMismatch { len, counted } => {
+                write!(f, "len is {} but level 0 has {} nodes", len, counted)
+            }
+        }
+    }
+}
+
+impl Error for InvariantError {}
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord,
+{
+    /// Verifies the list's structure, returning the first violation found.
+    ///
+    /// Checks that:
+    ///
+    /// * the head has exactly `max` levels,
+    /// * level 0 is strictly sorted (so keys are unique),
+    /// * every node is only linked into levels its tower reaches, and no tower
+    ///   is taller than `max`,
+    /// * every level is a subsequence of the level below it,
+    /// * level-0 back links and the tail pointer agree with the forward links,
+    /// * `len` matches the number of nodes at level 0.
+    ///
+    /// This walks every level, so it costs O(n log n); it is meant for tests
+    /// and debugging.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// for i in 0..100 {
+    ///     skiplist.insert(i, i);
+    /// }
+    /// skiplist.remove(&50);
+    ///
+    /// assert_eq!(skiplist.check_invariants(), Ok(()));
+    /// ```
+    pub fn check_invariants(&self) -> Result<(), InvariantError> {
+        let head_levels = self.head.read().unwrap().height();
+        if head_levels != self.max {
+            return Err(InvariantError::HeadLevels { head: head_levels, max: self.max });
+        }
+
+        self.check_level_zero()?;
+        for level in 1..self.max {
+            self.check_upper_level(level)?;
+        }
+        Ok(())
+    }
+
+    /// Check ordering, entries, back links, tail and length along level 0
+    fn check_level_zero(&self) -> Result<(), InvariantError> {
+        let mut prev: NodeRef<K, V> = Arc::clone(&self.head);
+        let mut next = self.head.read().unwrap().fwd[0].clone();
+        let mut index = 0;
+
+        while let Some(node) = next {
+            {
+                let node_ref = node.read().unwrap();
+                let prev_ref = prev.read().unwrap();
+
+                let Some(key) = node_ref.key.as_ref().filter(|_| node_ref.val.is_some()) else {
+                    return Err(InvariantError::MissingEntry { index });
+                };
+                if let Some(prev_key) = &prev_ref.key {
+                    if key < prev_key {
+                        return Err(InvariantError::Unsorted { index });
+                    }
+                    if key == prev_key {
+                        return Err(InvariantError::DuplicateKey { index });
+                    }
+                }
+                if node_ref.height() > self.max {
+                    let height = node_ref.height();
+                    return Err(InvariantError::TowerTooTall { index, height, max: self.max });
+                }
+
+                let back = node_ref.back.as_ref().and_then(Weak::upgrade);
+                if !back.is_some_and(|back| Arc::ptr_eq(&back, &prev)) {
+                    return Err(InvariantError::BrokenBackLink { index });
+                }
+                next = node_ref.fwd[0].clone();
+            }
+            prev = node;
+            index += 1;
+        }
+
+        let tail = self.tail.as_ref().and_then(Weak::upgrade);
+        let tail_ok = match tail {
+            Some(tail) => Arc::ptr_eq(&tail, &prev),
+            None => Arc::ptr_eq(&prev, &self.head),
+        };
+        if !tail_ok {
+            return Err(InvariantError::TailMismatch);
+        }
+
+        if index != self.len {
+            return Err(InvariantError::LengthMismatch { len: self.len, counted: index });
+        }
+        Ok(())
+    }
+
+    /// Check that `level` links exactly the nodes tall enough for it, in the
+    /// order of the level below
+    fn check_upper_level(&self, level: usize) -> Result<(), InvariantError> {
+        let mut below = self.head.read().unwrap().fwd[level - 1].clone();
+        let mut next = self.head.read().unwrap().fwd[level].clone();
+        let mut index = 0;
+
+        while let Some(node) = next {
+            if node.read().unwrap().height() <= level {
+                return Err(InvariantError::LinkAboveTower { level, index });
+            }
+
+            // Advance along the level below until it reaches the same node;
+            // nothing passed on the way may reach this level
+            loop {
+                let Some(candidate) = below else {
+                    return Err(InvariantError::NotSubsequence { level, index });
+                };
+                below = candidate.read().unwrap().fwd[level - 1].clone();
+                if Arc::ptr_eq(&candidate, &node) {
+                    break;
+                }
+                if candidate.read().unwrap().height() > level {
+                    return Err(InvariantError::SkippedTower { level, index });
+                }
+            }
+
+            next = node.read().unwrap().fwd[level].clone();
+            index += 1;
+        }
+
+        // Nor may anything after the last node at this level
+        while let Some(candidate) = below {
+            if candidate.read().unwrap().height() > level {
+                return Err(InvariantError::SkippedTower { level, index });
+            }
+            below = candidate.read().unwrap().fwd[level - 1].clone();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::RwLock;
+    use crate::Node;
+
+    /// A list whose towers all reach `max` (4 levels, since p is 1)
+    fn full_list(len: i32) -> SkipList<i32, i32> {
+        let mut list = SkipList::with_params(4, 1.0);
+        for i in 0..len {
+            list.insert(i, i);
+        }
+        assert_eq!(list.check_invariants(), Ok(()));
+        list
+    }
+
+    /// Nodes along level 0, in order
+    fn nodes(list: &SkipList<i32, i32>) -> Vec<NodeRef<i32, i32>> {
+        let mut nodes = Vec::new();
+        let mut next = list.head.read().unwrap().fwd[0].clone();
+        while let Some(node) = next {
+            next = node.read().unwrap().fwd[0].clone();
+            nodes.push(node);
+        }
+        nodes
+    }
+
+    #[test]
+    fn test_detects_wrong_len() {
+        let mut list = full_list(5);
+        list.len = 6;
+        assert_eq!(list.check_invariants(), Err(InvariantError::LengthMismatch { len: 6, counted: 5 }));
+    }
+
+    #[test]
+    fn test_detects_unsorted_and_duplicate_keys() {
+        let list = full_list(5);
+        nodes(&list)[3].write().unwrap().key = Some(1);
+        assert_eq!(list.check_invariants(), Err(InvariantError::Unsorted { index: 3 }));
+
+        nodes(&list)[3].write().unwrap().key = Some(2);
+        assert_eq!(list.check_invariants(), Err(InvariantError::DuplicateKey { index: 3 }));
+    }
+
+    #[test]
+    fn test_detects_missing_entry() {
+        let list = full_list(5);
+        nodes(&list)[2].write().unwrap().val = None;
+        assert_eq!(list.check_invariants(), Err(InvariantError::MissingEntry { index: 2 }));
+    }
+
+    #[test]
+    fn test_detects_upper_level_out_of_step_with_level_below() {
+        let list = full_list(3);
+        let [a, b, _] = <[_; 3]>::try_from(nodes(&list)).ok().unwrap();
+
+        // Level 1 visits b before a, so a is passed over at level 0 first
+        list.head.write().unwrap().fwd[1] = Some(Arc::clone(&b));
+        b.write().unwrap().fwd[1] = Some(Arc::clone(&a));
+        a.write().unwrap().fwd[1] = None;
+        assert_eq!(list.check_invariants(), Err(InvariantError::SkippedTower { level: 1, index: 0 }));
+    }
+
+    #[test]
+    fn test_detects_upper_level_node_missing_below() {
+        let list = full_list(3);
+        let last = Arc::clone(&nodes(&list)[2]);
+
+        // Linked at level 1 after the last node, but never at level 0
+        let stray = Arc::new(RwLock::new(Node::entry(9, 9, 4)));
+        last.write().unwrap().fwd[1] = Some(stray);
+        assert_eq!(list.check_invariants(), Err(InvariantError::NotSubsequence { level: 1, index: 3 }));
+    }
+
+    #[test]
+    fn test_detects_tower_skipped_at_upper_level() {
+        let list = full_list(3);
+        let [a, _, c] = <[_; 3]>::try_from(nodes(&list)).ok().unwrap();
+        a.write().unwrap().fwd[1] = Some(Arc::clone(&c));
+        assert_eq!(list.check_invariants(), Err(InvariantError::SkippedTower { level: 1, index: 1 }));
+
+        // A tall tower after the last node at a level is skipped too
+        let list = full_list(3);
+        let [_, b, _] = <[_; 3]>::try_from(nodes(&list)).ok().unwrap();
+        b.write().unwrap().fwd[2] = None;
+        assert_eq!(list.check_invariants(), Err(InvariantError::SkippedTower { level: 2, index: 2 }));
+    }
+
+    #[test]
+    fn test_detects_link_above_tower() {
+        let list = full_list(3);
+        let first = Arc::clone(&nodes(&list)[0]);
+        first.write().unwrap().fwd.truncate(1);
+        assert_eq!(list.check_invariants(), Err(InvariantError::LinkAboveTower { level: 1, index: 0 }));
+    }
+
+    #[test]
+    fn test_detects_tower_taller_than_max() {
+        let list = full_list(3);
+        nodes(&list)[1].write().unwrap().fwd.push(None);
+        assert_eq!(list.check_invariants(), Err(InvariantError::TowerTooTall { index: 1, height: 5, max: 4 }));
+    }
+
+    #[test]
+    fn test_detects_head_levels_mismatch() {
+        let list = full_list(3);
+        list.head.write().unwrap().fwd.push(None);
+        assert_eq!(list.check_invariants(), Err(InvariantError::HeadLevels { head: 5, max: 4 }));
+    }
+
+    #[test]
+    fn test_detects_stale_back_link() {
+        let list = full_list(5);
+        nodes(&list)[2].write().unwrap().back = Some(Arc::downgrade(&list.head));
+        assert_eq!(list.check_invariants(), Err(InvariantError::BrokenBackLink { index: 2 }));
+    }
+
+    #[test]
+    fn test_detects_stale_tail() {
+        let mut list = full_list(5);
+        list.tail = Some(Arc::downgrade(&nodes(&list)[3]));
+        assert_eq!(list.check_invariants(), Err(InvariantError::TailMismatch));
+
+        let mut empty: SkipList<i32, i32> = SkipList::new();
+        empty.tail = Some(Arc::downgrade(&nodes(&list)[0]));
+        assert_eq!(empty.check_invariants(), Err(InvariantError::TailMismatch));
+    }
+}
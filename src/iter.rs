@@ -0,0 +1,200 @@
+//! Double-ended iteration over a [`SkipList`].
+//!
+//! Iterators walk level 0 from both ends: forward through `fwd[0]` and
+//! backward through the level-0 back links, starting from the list's tail.
+//! Each step is O(1). Because values live behind the nodes' locks, entries
+//! are yielded as cloned `(K, V)` pairs, the same way [`SkipList::get`] does.
+
+use crate::{NodeRef, SkipList};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+use std::sync::{Arc, Weak};
+
+/// Iterator over the entries of a [`SkipList`] in key order.
+///
+/// Created by [`SkipList::iter`] and [`SkipList::range`]. Supports `.rev()`.
+pub struct Iter<'a, K, V> {
+    /// Next node to yield from the front
+    front: Option<NodeRef<K, V>>,
+    /// Next node to yield from the back
+    back: Option<NodeRef<K, V>>,
+    _list: PhantomData<&'a SkipList<K, V>>,
+}
+
+impl<K, V> Iter<'_, K, V> {
+    pub(crate) fn new(front: Option<NodeRef<K, V>>, back: Option<NodeRef<K, V>>) -> Self {
+        Iter {
+            front,
+            back,
+            _list: PhantomData,
+        }
+    }
+}
+
+impl<K, V> Iterator for Iter<'_, K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.front.take()?;
+        if self.back.as_ref().is_some_and(|back| Arc::ptr_eq(back, &node)) {
+            // Both ends met: this is the last entry
+            self.back = None;
+        } else {
+            self.front = node.read().unwrap().fwd[0].clone();
+        }
+
+        let node_ref = node.read().unwrap();
+        Some((node_ref.key.clone()?, node_ref.val.clone()?))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let node = self.back.take()?;
+        if self.front.as_ref().is_some_and(|front| Arc::ptr_eq(front, &node)) {
+            self.front = None;
+        } else {
+            self.back = node.read().unwrap().back.as_ref().and_then(Weak::upgrade);
+        }
+
+        let node_ref = node.read().unwrap();
+        Some((node_ref.key.clone()?, node_ref.val.clone()?))
+    }
+}
+
+impl<K, V> FusedIterator for Iter<'_, K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+}
+
+impl<'a, K, V> IntoIterator for &'a SkipList<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    type Item = (K, V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord,
+{
+    /// Returns an iterator over all entries in key order.
+    ///
+    /// The iterator is double-ended, so `iter().rev()` walks from the largest
+    /// key down.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.insert(2, "two");
+    /// skiplist.insert(1, "one");
+    /// skiplist.insert(3, "three");
+    ///
+    /// let keys: Vec<_> = skiplist.iter().map(|(k, _)| k).collect();
+    /// assert_eq!(keys, vec![1, 2, 3]);
+    ///
+    /// // Latest two entries
+    /// let latest: Vec<_> = skiplist.iter().rev().take(2).collect();
+    /// assert_eq!(latest, vec![(3, "three"), (2, "two")]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let front = self.head.read().unwrap().fwd[0].clone();
+        let back = self.tail.as_ref().and_then(Weak::upgrade);
+        Iter::new(front, back)
+    }
+
+    /// Returns an iterator over the entries whose keys fall in `range`.
+    ///
+    /// Finding each end costs one search; every step after that is O(1) in
+    /// either direction. A range whose start is past its end is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// for i in 0..10 {
+    ///     skiplist.insert(i, i * 10);
+    /// }
+    ///
+    /// let keys: Vec<_> = skiplist.range(3..6).map(|(k, _)| k).collect();
+    /// assert_eq!(keys, vec![3, 4, 5]);
+    ///
+    /// let keys: Vec<_> = skiplist.range(..=2).rev().map(|(k, _)| k).collect();
+    /// assert_eq!(keys, vec![2, 1, 0]);
+    /// ```
+    pub fn range<R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        let front = match range.start_bound() {
+            Bound::Included(start) => self.first_at_least(start, true),
+            Bound::Excluded(start) => self.first_at_least(start, false),
+            Bound::Unbounded => self.head.read().unwrap().fwd[0].clone(),
+        };
+        let back = match range.end_bound() {
+            Bound::Included(end) => self.last_at_most(end, true),
+            Bound::Excluded(end) => self.last_at_most(end, false),
+            Bound::Unbounded => self.tail.as_ref().and_then(Weak::upgrade),
+        };
+
+        // An empty range leaves the ends crossed over
+        let crossed = match (&front, &back) {
+            (Some(front), Some(back)) => front.read().unwrap().key > back.read().unwrap().key,
+            _ => true,
+        };
+        if crossed {
+            return Iter::new(None, None);
+        }
+        Iter::new(front, back)
+    }
+
+    /// First node with a key above `key` (or equal to it, if `inclusive`)
+    pub(crate) fn first_at_least(&self, key: &K, inclusive: bool) -> Option<NodeRef<K, V>> {
+        let update = self.find_predecessors(key);
+        let next = update[0].read().unwrap().fwd[0].clone()?;
+        if !inclusive && next.read().unwrap().key.as_ref() == Some(key) {
+            return next.read().unwrap().fwd[0].clone();
+        }
+        Some(next)
+    }
+
+    /// Last node with a key below `key` (or equal to it, if `inclusive`)
+    pub(crate) fn last_at_most(&self, key: &K, inclusive: bool) -> Option<NodeRef<K, V>> {
+        let update = self.find_predecessors(key);
+        if inclusive {
+            let next = update[0].read().unwrap().fwd[0].clone();
+            if let Some(next) = next {
+                if next.read().unwrap().key.as_ref() == Some(key) {
+                    return Some(next);
+                }
+            }
+        }
+        let pred = Arc::clone(&update[0]);
+        if Arc::ptr_eq(&pred, &self.head) {
+            return None;
+        }
+        Some(pred)
+    }
+}
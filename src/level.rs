@@ -0,0 +1,32 @@
+//! Tower heights, shared by every skip list in the crate.
+//!
+//! All of them draw heights the same way, so the same seed and parameters
+//! give the same tower shapes whichever structure the entries go into.
+
+use rand::rngs::StdRng;
+use rand::Rng;
+
+/// Flip coins with probability `p` until one fails or `max` is reached,
+/// using the seeded generator if there is one and the thread RNG otherwise
+pub(crate) fn level_from(rng: &mut Option<StdRng>, p: f64, max: usize) -> usize {
+    match rng {
+        Some(rng) => flip(rng, p, max),
+        None => flip(&mut rand::thread_rng(), p, max),
+    }
+}
+
+fn flip<R: Rng>(rng: &mut R, p: f64, max: usize) -> usize {
+    let mut lvl = 1;
+    while rng.gen_bool(p) && lvl < max {
+        lvl += 1;
+    }
+    lvl
+}
+
+/// Levels a list of `len` entries should have: about log2(len) + 2
+pub(crate) fn optimal_levels(len: usize) -> usize {
+    if len == 0 {
+        return 1;
+    }
+    ((len as f64).log2().ceil() as usize) + 2
+}
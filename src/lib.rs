@@ -26,17 +26,81 @@
 //! ```
 //!
 
-use rand::Rng;
-use std::sync::{Arc, RwLock};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use std::cmp::Ordering;
+use std::sync::atomic::AtomicU64;
+use bounded::Bounds;
+use sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, Weak};
 
-type Link<K, V> = Option<Arc<RwLock<Node<K, V>>>>;
+/// Bump one of a list's metrics counters; expands to nothing without the
+/// `metrics` feature
+macro_rules! record {
+    ($list:expr, $counter:ident) => {{
+        #[cfg(feature = "metrics")]
+        $list
+            .metrics
+            .$counter
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }};
+}
+
+pub mod augmented;
+mod batch;
+mod bounded;
+pub mod cursor;
+pub mod drain;
+pub mod finger;
+pub mod interchange;
+pub mod interval;
+pub mod invariants;
+pub mod iter;
+mod level;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod persistent;
+pub mod prefix;
+pub mod seq;
+pub mod snapshot;
+mod split;
+pub mod stats;
+mod sync;
+pub mod trace;
+pub mod ttl;
+pub mod visualize;
+
+pub use augmented::{AugmentedSkipList, Count, Max, Min, Monoid, Sum};
+pub use bounded::{Capacity, EvictionPolicy, Inserted};
+pub use cursor::{Cursor, CursorMut, UnorderedKeyError};
+pub use drain::{Drain, ExtractIf};
+pub use finger::Finger;
+pub use interchange::{DuplicatePolicy, ExportOptions, Format, ImportError, ImportErrorKind, ImportOptions, ImportSummary, TextKey};
+pub use interval::{IntervalEntry, IntervalId, IntervalSkipList};
+pub use invariants::InvariantError;
+pub use iter::Iter;
+#[cfg(feature = "metrics")]
+pub use metrics::Metrics;
+pub use persistent::PersistentSkipList;
+pub use prefix::PrefixIter;
+pub use seq::SkipSeq;
+pub use snapshot::{SnapshotError, SnapshotValue};
+pub use stats::SkipListStats;
+pub use trace::{LevelTrace, SearchObserver, SearchTrace};
+pub use ttl::{Clock, ManualClock, SystemClock, TtlSkipList};
+pub use visualize::RenderOptions;
+
+type NodeRef<K, V> = Arc<RwLock<Node<K, V>>>;
+type Link<K, V> = Option<NodeRef<K, V>>;
 
 /// Internal node structure for the skip list
 struct Node<K, V> {
     key: Option<K>,
     val: Option<V>,
     fwd: Vec<Link<K, V>>,
+    /// Level-0 predecessor (`None` for the head)
+    back: Option<Weak<RwLock<Node<K, V>>>>,
+    /// Stamp of the last access, for least-recently-used eviction
+    accessed: AtomicU64,
 }
 
 impl<K, V> Node<K, V> {
@@ -46,6 +110,8 @@ impl<K, V> Node<K, V> {
             key: None, 
             val: None, 
             fwd: vec![None; max_levels],
+            back: None,
+            accessed: AtomicU64::new(0),
         }
     }
 
@@ -55,8 +121,15 @@ impl<K, V> Node<K, V> {
             key: Some(key),
             val: Some(val),
             fwd: vec![None; level],
+            back: None,
+            accessed: AtomicU64::new(0),
         }
-    }    
+    }
+
+    /// Number of levels this node is linked into
+    fn height(&self) -> usize {
+        self.fwd.len()
+    }
 }
 
 /// A thread-safe skip list with dynamic level management.
@@ -67,10 +140,21 @@ impl<K, V> Node<K, V> {
 /// This implementation automatically adjusts its level structure based on the number
 /// of elements to maintain optimal performance characteristics.
 pub struct SkipList<K, V> {
-    head: Arc<RwLock<Node<K, V>>>,
+    head: NodeRef<K, V>,
+    /// Last node at level 0 (`None` when empty)
+    tail: Option<Weak<RwLock<Node<K, V>>>>,
     max: usize,
     len: usize,
     p: f64,
+    /// Level generator set by [`seed`](SkipList::seed) (the thread RNG otherwise)
+    rng: Option<StdRng>,
+    /// Capacity limit, eviction policy and usage bookkeeping
+    bounds: Bounds<K, V>,
+    /// Bumped when nodes move between lists, so a [`Finger`] into this list
+    /// knows its path is stale
+    epoch: u64,
+    #[cfg(feature = "metrics")]
+    metrics: Metrics,
 }
 
 impl<K, V> Default for SkipList<K, V>
@@ -119,9 +203,15 @@ where
     pub fn with_params(initial_max: usize, p: f64) -> Self {
         Self {
             head: Arc::new(RwLock::new(Node::head(initial_max))),
+            tail: None,
             max: initial_max,
             len: 0,
             p,
+            rng: None,
+            bounds: Bounds::default(),
+            epoch: 0,
+            #[cfg(feature = "metrics")]
+            metrics: Metrics::default(),
         }
     }
 
@@ -156,18 +246,51 @@ where
         self.len == 0
     }
 
-    /// Calculate optimal max level based on current number of records
-    fn optimal_levels(&self) -> usize {
-        if self.len == 0 {
-            return 1;
-        }
-        let optimal = ((self.len as f64).log2().ceil() as usize) + 2;
-        optimal.max(1)
+    /// Returns the list's operation counters.
+    ///
+    /// Only available with the `metrics` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.insert(1, "one");
+    /// skiplist.insert(1, "ONE");
+    /// skiplist.get(&2);
+    ///
+    /// let metrics = skiplist.metrics();
+    /// assert_eq!(metrics.inserts(), 1);
+    /// assert_eq!(metrics.updates(), 1);
+    /// assert_eq!(metrics.misses(), 1);
+    /// ```
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Read-lock `node`, counting contention when metrics are enabled
+    #[inline]
+    fn read_node<'a>(&self, node: &'a NodeRef<K, V>) -> RwLockReadGuard<'a, Node<K, V>> {
+        #[cfg(feature = "metrics")]
+        return self.metrics.read(node);
+        #[cfg(not(feature = "metrics"))]
+        node.read().unwrap()
+    }
+
+    /// Write-lock `node`, counting contention when metrics are enabled
+    #[inline]
+    fn write_node<'a>(&self, node: &'a NodeRef<K, V>) -> RwLockWriteGuard<'a, Node<K, V>> {
+        #[cfg(feature = "metrics")]
+        return self.metrics.write(node);
+        #[cfg(not(feature = "metrics"))]
+        node.write().unwrap()
     }
 
     /// Dynamically adjust max level if needed
     fn resize(&mut self) {
-        let optimal = self.optimal_levels();
+        let optimal = level::optimal_levels(self.len);
         if optimal > self.max {
             self.grow(optimal);
         }
@@ -176,20 +299,43 @@ where
     /// Grow the head node to accommodate more levels
     fn grow(&mut self, new_max: usize) {
         let mut head = self.head.write().unwrap();
+        if head.fwd.len() < new_max {
+            record!(self, resizes);
+        }
         while head.fwd.len() < new_max {
             head.fwd.push(None);
         }
         self.max = new_max;
     }
 
+    /// Reseeds the generator that picks tower heights.
+    ///
+    /// Two lists seeded alike and given the same operations end up with the
+    /// same shape, which makes experiments and model-checking runs
+    /// reproducible. Unseeded lists use the thread-local RNG.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut a = SkipList::new();
+    /// let mut b = SkipList::new();
+    /// a.seed(42);
+    /// b.seed(42);
+    /// for i in 0..100 {
+    ///     a.insert(i, i);
+    ///     b.insert(i, i);
+    /// }
+    /// assert_eq!(a.to_ascii(), b.to_ascii());
+    /// ```
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = Some(StdRng::seed_from_u64(seed));
+    }
+
     /// Generate a random level for a new node
-    fn random_level(&self) -> usize {
-        let mut rng = rand::thread_rng();
-        let mut lvl = 1; 
-        while rng.gen_bool(self.p) && lvl < self.max {
-            lvl += 1;
-        }
-        lvl
+    fn random_level(&mut self) -> usize {
+        level::level_from(&mut self.rng, self.p, self.max)
     }
     
     /// Inserts a key-value pair into the skip list.
@@ -223,25 +369,128 @@ where
     where
         V: Clone,
     {
-        let mut update = vec![None; self.max];
+        self.insert_observed(key, value, &mut ())
+    }
+
+    /// Inserts a key-value pair, reporting each step of the search for the
+    /// key's position to `observer`.
+    ///
+    /// Behaves exactly like [`insert`](Self::insert); see [`SearchObserver`]
+    /// for the events reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::{SearchTrace, SkipList};
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.insert(1, "one");
+    ///
+    /// let mut trace = SearchTrace::default();
+    /// skiplist.insert_observed(2, "two", &mut trace);
+    /// assert!(trace.comparisons() >= 1);
+    /// ```
+    pub fn insert_observed<O>(&mut self, key: K, value: V, observer: &mut O) -> Option<V>
+    where
+        O: SearchObserver,
+    {
+        let old = self.insert_entry(key, value, observer);
+        self.evict_to_capacity();
+        old
+    }
+
+    /// Insert or update without enforcing the capacity
+    fn insert_entry<O>(&mut self, key: K, value: V, observer: &mut O) -> Option<V>
+    where
+        O: SearchObserver,
+    {
+        let update = self.find_predecessors_observed(&key, observer);
+        self.insert_after(&update, key, value)
+    }
+
+    /// Insert or update given the key's predecessors at every level
+    fn insert_after(&mut self, update: &[NodeRef<K, V>], key: K, value: V) -> Option<V> {
+        // Check if key already exists
+        if let Some(next) = update[0].read().unwrap().fwd[0].clone() {
+            let mut next_ref = next.write().unwrap();
+            if let Some(existing_key) = &next_ref.key {
+                if existing_key == &key {
+                    record!(self, updates);
+                    let old = next_ref.val.replace(value);
+                    if let Some(old) = &old {
+                        self.bounds.updated(&next_ref, old);
+                    }
+                    return old;
+                }
+            }
+        }
+
+        // Insert phase: create new node and link it in
+        let height = self.random_level();
+        self.link_after(&update[..height], key, value);
+        None
+    }
+
+    /// Removes a key from the skip list, returning its value if it was present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.insert(1, "one");
+    ///
+    /// assert_eq!(skiplist.remove(&1), Some("one"));
+    /// assert_eq!(skiplist.remove(&1), None);
+    /// assert!(skiplist.is_empty());
+    /// ```
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let update = self.find_predecessors(key);
+
+        let node = update[0].read().unwrap().fwd[0].clone()?;
+        if node.read().unwrap().key.as_ref() != Some(key) {
+            return None;
+        }
+
+        self.unlink(&update, &node).map(|(_, val)| val)
+    }
+
+    /// Find the rightmost node before `key` at every level
+    fn find_predecessors(&self, key: &K) -> Vec<NodeRef<K, V>> {
+        self.find_predecessors_observed(key, &mut ())
+    }
+
+    /// Find the rightmost node before `key` at every level, reporting each
+    /// step of the search to `observer`
+    fn find_predecessors_observed<O>(&self, key: &K, observer: &mut O) -> Vec<NodeRef<K, V>>
+    where
+        O: SearchObserver,
+    {
+        let mut update = Vec::with_capacity(self.max);
         let mut curr = Arc::clone(&self.head);
 
         // Search phase: find predecessors at each level
         for level in (0..self.max).rev() {
+            observer.on_descend(level);
             loop {
                 let next = {
-                    let curr_ref = curr.read().unwrap();
+                    observer.on_lock(level);
+                    let curr_ref = self.read_node(&curr);
                     curr_ref.fwd[level].clone()
                 };
 
                 match next {
                     Some(node) => {
                         let should_advance = {
-                            let node_ref = node.read().unwrap();
-                            node_ref.key.as_ref().unwrap() < &key
+                            observer.on_lock(level);
+                            let node_ref = self.read_node(&node);
+                            observer.on_compare(level);
+                            node_ref.key.as_ref().unwrap() < key
                         };
 
                         if should_advance {
+                            observer.on_step(level);
                             curr = node;
                         } else {
                             break;
@@ -250,32 +499,144 @@ where
                     None => break,
                 }
             }
-            update[level] = Some(Arc::clone(&curr));
+            update.push(Arc::clone(&curr));
         }
 
-        // Check if key already exists
-        if let Some(next) = update[0].as_ref().unwrap().read().unwrap().fwd[0].clone() {
-            let mut next_ref = next.write().unwrap();
-            if let Some(existing_key) = &next_ref.key {
-                if existing_key == &key {
-                    return next_ref.val.replace(value);
+        update.reverse();
+        update
+    }
+
+    /// Find the node holding `key`, reporting each step of the search to
+    /// `observer`. Stops as soon as the key is seen on any level.
+    fn find_node_observed<O>(&self, key: &K, observer: &mut O) -> Option<NodeRef<K, V>>
+    where
+        O: SearchObserver,
+    {
+        let mut curr = Arc::clone(&self.head);
+
+        for level in (0..self.max).rev() {
+            observer.on_descend(level);
+            loop {
+                let next = {
+                    observer.on_lock(level);
+                    let curr_ref = self.read_node(&curr);
+                    curr_ref.fwd[level].clone()
+                };
+
+                match next {
+                    Some(node) => {
+                        let ordering = {
+                            observer.on_lock(level);
+                            let node_ref = self.read_node(&node);
+                            observer.on_compare(level);
+                            node_ref.key.as_ref().map(|k| k.cmp(key))
+                        };
+                        match ordering {
+                            Some(Ordering::Less) => {
+                                observer.on_step(level);
+                                curr = node;
+                            }
+                            Some(Ordering::Equal) => {
+                                return Some(node);
+                            }
+                            _ => break,
+                        }
+                    }
+                    None => break,
                 }
             }
         }
+        None
+    }
 
-        // Insert phase: create new node and link it in
-        let new_level = self.random_level();
-        let new_node = Arc::new(RwLock::new(Node::entry(key, value, new_level)));
+    /// Link a new node directly after the given per-level predecessors.
+    ///
+    /// `update[level]` must be the node the new entry follows at `level`; the
+    /// new tower is as tall as `update` is long.
+    fn link_after(&mut self, update: &[NodeRef<K, V>], key: K, value: V) -> NodeRef<K, V> {
+        let new_node = Arc::new(RwLock::new(Node::entry(key, value, update.len())));
 
-        for level in 0..new_level {
-            let mut prev = update[level].as_ref().unwrap().write().unwrap();
-            new_node.write().unwrap().fwd[level] = prev.fwd[level].take();
+        for (level, pred) in update.iter().enumerate() {
+            let mut prev = self.write_node(pred);
+            let mut node = new_node.write().unwrap();
+            node.fwd[level] = prev.fwd[level].take();
             prev.fwd[level] = Some(Arc::clone(&new_node));
+
+            if level == 0 {
+                node.back = Some(Arc::downgrade(pred));
+                match &node.fwd[0] {
+                    Some(next) => next.write().unwrap().back = Some(Arc::downgrade(&new_node)),
+                    None => self.tail = Some(Arc::downgrade(&new_node)),
+                }
+            }
         }
 
         self.len += 1;
+        self.bounds.linked(&new_node);
+        record!(self, inserts);
         self.resize();
-        None
+        new_node
+    }
+
+    /// Unlink `node` given its predecessors, returning its entry.
+    ///
+    /// `update[level]` must point at `node` for every level of its tower.
+    fn unlink(&mut self, update: &[NodeRef<K, V>], node: &NodeRef<K, V>) -> Option<(K, V)> {
+        let height = node.read().unwrap().height();
+
+        // Locks are always taken left to right: predecessor, node, successor
+        for (level, pred) in update.iter().enumerate().take(height) {
+            let mut prev = self.write_node(pred);
+            prev.fwd[level] = self.write_node(node).fwd[level].take();
+
+            if level == 0 {
+                match &prev.fwd[0] {
+                    Some(next) => next.write().unwrap().back = Some(Arc::downgrade(pred)),
+                    None if Arc::ptr_eq(pred, &self.head) => self.tail = None,
+                    None => self.tail = Some(Arc::downgrade(pred)),
+                }
+            }
+        }
+
+        self.len -= 1;
+        record!(self, removes);
+        let mut node_ref = node.write().unwrap();
+        self.bounds.unlinked(&node_ref);
+        node_ref.back = None;
+        Some((node_ref.key.take()?, node_ref.val.take()?))
+    }
+
+    /// Walk level-0 back links from `start` to collect predecessors for a
+    /// tower of `height` levels.
+    ///
+    /// The first node (`start` included) tall enough for each level is the
+    /// predecessor at that level, so this never needs to search from the head.
+    fn predecessors_from(start: &NodeRef<K, V>, height: usize) -> Vec<NodeRef<K, V>> {
+        let mut update = Vec::with_capacity(height);
+        let mut curr = Arc::clone(start);
+
+        for level in 0..height {
+            loop {
+                let back = {
+                    let curr_ref = curr.read().unwrap();
+                    if curr_ref.height() > level {
+                        break;
+                    }
+                    curr_ref.back.as_ref().and_then(Weak::upgrade)
+                };
+                curr = back.expect("the head is tall enough for every level");
+            }
+            update.push(Arc::clone(&curr));
+        }
+        update
+    }
+
+    /// Find the last node in the list, or the head when it is empty
+    fn last_node(&self) -> NodeRef<K, V> {
+        self.tail
+            .as_ref()
+            .and_then(Weak::upgrade)
+            .unwrap_or_else(|| Arc::clone(&self.head))
     }
 
     /// Retrieves a value by its key.
@@ -303,35 +664,7 @@ where
     where 
         V: Clone 
     {
-        let mut curr = Arc::clone(&self.head);
-
-        for level in (0..self.max).rev() {
-            loop {
-                let next = {
-                    let curr_ref = curr.read().unwrap();
-                    curr_ref.fwd[level].clone()
-                };
-
-                match next {
-                    Some(node) => {
-                        match {
-                            let node_ref = node.read().unwrap();
-                            node_ref.key.as_ref().map(|k| k.cmp(key)) 
-                        } {
-                            Some(Ordering::Less) => {
-                                curr = node;
-                            }
-                            Some(Ordering::Equal) => {
-                                return node.read().unwrap().val.clone();
-                            }
-                            _ => break,
-                        }
-                    }
-                    None => break,
-                }
-            }
-        }
-        None
+        self.get_observed(key, &mut ())
     }
 
     /// Returns true if the skip list contains the specified key.
@@ -1,26 +1,20 @@
-use skiplist_rs::SkipList;
+//! `skiplist-rs` command-line tool.
+//!
+//! Run without arguments for an interactive shell over a `SkipList<i64, String>`;
+//! see `skiplist-rs help`.
 
-fn main() {
-    let mut skiplist = SkipList::new();
+mod cli;
 
-    println!("Inserting values into skip list...");
-    skiplist.insert(5, "five");
-    skiplist.insert(1, "one");
-    skiplist.insert(3, "three");
-    skiplist.insert(7, "seven");
-    skiplist.insert(2, "two");
+use std::env;
+use std::process::ExitCode;
 
-    println!("\nSearching for values:");
-    for key in 1..=7 {
-        match skiplist.get(&key) {
-            Some(value) => println!("Key {}: {}", key, value),
-            None => println!("Key {}: Not found", key),
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match cli::run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
         }
     }
-
-    let old_value = skiplist.insert(3, "THREE");
-    println!("\nUpdated key 3. Old value: {:?}", old_value);
-    println!("New value: {:?}", skiplist.get(&3));
-
-    println!("\nFinal stats: {} elements", skiplist.len());
 }
@@ -0,0 +1,153 @@
+//! Operation counters, enabled with the `metrics` feature.
+//!
+//! Every [`SkipList`](crate::SkipList) built with the feature carries a
+//! [`Metrics`] block of atomic counters, bumped as operations run and read
+//! through [`SkipList::metrics`](crate::SkipList::metrics). Without the
+//! feature the counters and every update to them are compiled out.
+//!
+//! [`Metrics::to_prometheus`] renders the counters in the Prometheus text
+//! exposition format so they can be served from an existing `/metrics`
+//! endpoint.
+
+use crate::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::TryLockError;
+
+/// Atomic operation counters for one list.
+///
+/// Counters only ever go up; they start at zero when the list is created.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    pub(crate) inserts: AtomicU64,
+    pub(crate) updates: AtomicU64,
+    pub(crate) hits: AtomicU64,
+    pub(crate) misses: AtomicU64,
+    pub(crate) removes: AtomicU64,
+    pub(crate) resizes: AtomicU64,
+    pub(crate) lock_contention: AtomicU64,
+}
+
+impl Metrics {
+    /// Entries inserted under a key that was not in the list.
+    pub fn inserts(&self) -> u64 {
+        self.inserts.load(Ordering::Relaxed)
+    }
+
+    /// Values replaced under a key that was already in the list.
+    pub fn updates(&self) -> u64 {
+        self.updates.load(Ordering::Relaxed)
+    }
+
+    /// Lookups that found their key.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Lookups that did not find their key.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Entries removed, whether one at a time or in bulk.
+    pub fn removes(&self) -> u64 {
+        self.removes.load(Ordering::Relaxed)
+    }
+
+    /// Times the head grew new levels.
+    pub fn resizes(&self) -> u64 {
+        self.resizes.load(Ordering::Relaxed)
+    }
+
+    /// Node lock acquisitions during searches and relinking that could not
+    /// be taken immediately and had to wait.
+    pub fn lock_contention(&self) -> u64 {
+        self.lock_contention.load(Ordering::Relaxed)
+    }
+
+    /// Renders every counter in the Prometheus text exposition format.
+    ///
+    /// Metric names are prefixed with `skiplist_`, and `labels` are attached
+    /// to every sample so several lists can be exported side by side.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.insert(1, "one");
+    /// skiplist.get(&1);
+    ///
+    /// let text = skiplist.metrics().to_prometheus(&[("list", "sessions")]);
+    /// assert!(text.contains("skiplist_inserts_total{list=\"sessions\"} 1\n"));
+    /// assert!(text.contains("# TYPE skiplist_hits_total counter\n"));
+    /// ```
+    pub fn to_prometheus(&self, labels: &[(&str, &str)]) -> String {
+        let labels = if labels.is_empty() {
+            String::new()
+        } else {
+            let pairs: Vec<String> = labels
+                .iter()
+                .map(|(name, value)| format!("{}=\"{}\"", name, escape_label(value)))
+                .collect();
+            format!("{{{}}}", pairs.join(","))
+        };
+
+        let counters = [
+            ("inserts", "Entries inserted under a new key.", self.inserts()),
+            ("updates", "Values replaced under an existing key.", self.updates()),
+            ("hits", "Lookups that found their key.", self.hits()),
+            ("misses", "Lookups that did not find their key.", self.misses()),
+            ("removes", "Entries removed.", self.removes()),
+            ("resizes", "Times the head grew new levels.", self.resizes()),
+            ("lock_contention", "Node lock acquisitions that had to wait.", self.lock_contention()),
+        ];
+
+        let mut out = String::new();
+        for (name, help, value) in counters {
+            let _ = writeln!(out, "# HELP skiplist_{}_total {}", name, help);
+            let _ = writeln!(out, "# TYPE skiplist_{}_total counter", name);
+            let _ = writeln!(out, "skiplist_{}_total{} {}", name, labels, value);
+        }
+        out
+    }
+
+    /// Read-lock `lock`, counting the acquisition if it has to wait
+    pub(crate) fn read<'a, T>(&self, lock: &'a RwLock<T>) -> RwLockReadGuard<'a, T> {
+        match lock.try_read() {
+            Ok(guard) => guard,
+            Err(TryLockError::WouldBlock) => {
+                self.lock_contention.fetch_add(1, Ordering::Relaxed);
+                lock.read().unwrap()
+            }
+            Err(TryLockError::Poisoned(err)) => panic!("{}", err),
+        }
+    }
+
+    /// Write-lock `lock`, counting the acquisition if it has to wait
+    pub(crate) fn write<'a, T>(&self, lock: &'a RwLock<T>) -> RwLockWriteGuard<'a, T> {
+        match lock.try_write() {
+            Ok(guard) => guard,
+            Err(TryLockError::WouldBlock) => {
+                self.lock_contention.fetch_add(1, Ordering::Relaxed);
+                lock.write().unwrap()
+            }
+            Err(TryLockError::Poisoned(err)) => panic!("{}", err),
+        }
+    }
+}
+
+/// Escape a label value for the text exposition format
+fn escape_label(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,487 @@
+//! Persistent (immutable) skip list variant.
+//!
+//! A [`PersistentSkipList`] never changes once built: `insert` and `remove`
+//! return a new version and leave the old one untouched. Versions share every
+//! part of the structure they have in common through `Arc`, so cloning is O(1)
+//! and each update only copies the nodes on its search path.
+//!
+//! ## Why the layout differs from [`SkipList`](crate::SkipList)
+//!
+//! Versions can't be built from the mutable list's `Arc`-linked `Node`s. A
+//! node is shared by every version that can reach it, so it can never change;
+//! but in a forward-linked chain every node is reachable from its level-0
+//! predecessor, so changing one `fwd` pointer means copying the predecessor,
+//! then *its* predecessor, and so on back to the head: O(n) per update instead
+//! of O(log n). The `RwLock` and `back` link each `Node` carries would also be
+//! dead weight in a structure that is never written after it is built.
+//!
+//! So the same tower structure is stored "sideways" as a tree of segments.
+//! Tower heights and the number of levels still come from the same helpers
+//! the mutable list uses, so both have the same expected shape.
+//!
+//! ```text
+//! Level 2: [H] ------------------------------> [15]
+//! Level 1: [H] -> [4] --------> [9] ---------> [15]
+//! Level 0: [H] -> [4] -> [6] -> [9] -> [12] -> [15]
+//!
+//! Level 1 segments:  H{}  4{6}  9{12}  15{}
+//! Level 2 segments:  H{ H{}, 4{6}, 9{12} }  15{ 15{} }
+//! ```
+//!
+//! The stretch of level `n` that starts at a tower and ends at the next tower of
+//! the same height is that tower's *segment*. It splits into the level `n - 1`
+//! segments of the towers it skips over, which is exactly a tree whose depth is
+//! the number of levels. Updating a key therefore copies one segment per level.
+
+use crate::level;
+use std::sync::Arc;
+
+type Entry<K, V> = Arc<(K, V)>;
+
+/// One tower's stretch of a single level.
+struct Segment<K, V> {
+    /// Entry whose tower starts this segment (`None` for the head tower)
+    owner: Option<Entry<K, V>>,
+    body: Body<K, V>,
+}
+
+enum Body<K, V> {
+    /// Level 1: the height-1 entries that follow the owner at level 0
+    Entries(Vec<Entry<K, V>>),
+    /// Level 2 and above: segments one level down, starting with the owner's own
+    Lanes(Vec<Arc<Segment<K, V>>>),
+}
+
+impl<K: Ord, V> Segment<K, V> {
+    /// Create an empty head segment spanning `level` levels
+    fn head(level: usize) -> Self {
+        let body = if level <= 1 {
+            Body::Entries(Vec::new())
+        } else {
+            Body::Lanes(vec![Arc::new(Segment::head(level - 1))])
+        };
+        Segment { owner: None, body }
+    }
+
+    /// Key of the entry that starts this segment
+    fn key(&self) -> Option<&K> {
+        self.owner.as_ref().map(|entry| &entry.0)
+    }
+
+    /// Index of the last lane starting at or before `key`
+    fn lane_for(lanes: &[Arc<Segment<K, V>>], key: &K) -> usize {
+        let mut i = 0;
+        while i + 1 < lanes.len() && lanes[i + 1].key().is_some_and(|k| k <= key) {
+            i += 1;
+        }
+        i
+    }
+
+    /// Copy of this segment with the owner swapped for `entry`
+    fn with_owner(&self, entry: Entry<K, V>) -> Self {
+        let body = match &self.body {
+            Body::Entries(entries) => Body::Entries(entries.clone()),
+            Body::Lanes(lanes) => {
+                let mut lanes = lanes.clone();
+                lanes[0] = Arc::new(lanes[0].with_owner(Arc::clone(&entry)));
+                Body::Lanes(lanes)
+            }
+        };
+        Segment { owner: Some(entry), body }
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        if self.key() == Some(key) {
+            return self.owner.as_ref().map(|entry| &entry.1);
+        }
+        match &self.body {
+            Body::Entries(entries) => entries
+                .binary_search_by(|entry| entry.0.cmp(key))
+                .ok()
+                .map(|i| &entries[i].1),
+            Body::Lanes(lanes) => lanes[Self::lane_for(lanes, key)].get(key),
+        }
+    }
+
+    /// Replace the value of an existing key, returning the new segment
+    fn replace(&self, entry: Entry<K, V>) -> Self {
+        if self.key() == Some(&entry.0) {
+            return self.with_owner(entry);
+        }
+        let body = match &self.body {
+            Body::Entries(entries) => {
+                let mut entries = entries.clone();
+                if let Ok(i) = entries.binary_search_by(|e| e.0.cmp(&entry.0)) {
+                    entries[i] = entry;
+                }
+                Body::Entries(entries)
+            }
+            Body::Lanes(lanes) => {
+                let mut lanes = lanes.clone();
+                let i = Self::lane_for(&lanes, &entry.0);
+                lanes[i] = Arc::new(lanes[i].replace(entry));
+                Body::Lanes(lanes)
+            }
+        };
+        Segment { owner: self.owner.clone(), body }
+    }
+
+    /// Insert a new key whose tower is `height` levels tall.
+    ///
+    /// Returns the updated segment and, when the new tower reaches this
+    /// `level`, the segment it now starts.
+    fn insert(&self, level: usize, entry: Entry<K, V>, height: usize) -> (Self, Option<Self>) {
+        match &self.body {
+            Body::Entries(entries) => {
+                let pos = entries.partition_point(|e| e.0 < entry.0);
+                if height > level {
+                    let left = Segment {
+                        owner: self.owner.clone(),
+                        body: Body::Entries(entries[..pos].to_vec()),
+                    };
+                    let right = Segment {
+                        owner: Some(entry),
+                        body: Body::Entries(entries[pos..].to_vec()),
+                    };
+                    (left, Some(right))
+                } else {
+                    let mut entries = entries.clone();
+                    entries.insert(pos, entry);
+                    let seg = Segment { owner: self.owner.clone(), body: Body::Entries(entries) };
+                    (seg, None)
+                }
+            }
+            Body::Lanes(lanes) => {
+                let i = Self::lane_for(lanes, &entry.0);
+                let (lower, split) = lanes[i].insert(level - 1, Arc::clone(&entry), height);
+
+                let mut left = lanes[..i].to_vec();
+                left.push(Arc::new(lower));
+                let mut right: Vec<_> = split.into_iter().map(Arc::new).collect();
+                right.extend(lanes[i + 1..].iter().cloned());
+
+                if height > level {
+                    let left = Segment { owner: self.owner.clone(), body: Body::Lanes(left) };
+                    let right = Segment { owner: Some(entry), body: Body::Lanes(right) };
+                    (left, Some(right))
+                } else {
+                    left.extend(right);
+                    (Segment { owner: self.owner.clone(), body: Body::Lanes(left) }, None)
+                }
+            }
+        }
+    }
+
+    /// Remove `key`, returning the new segment and the removed entry
+    fn remove(&self, key: &K) -> Option<(Self, Entry<K, V>)> {
+        let body = match &self.body {
+            Body::Entries(entries) => {
+                let i = entries.binary_search_by(|e| e.0.cmp(key)).ok()?;
+                let mut entries = entries.clone();
+                let removed = entries.remove(i);
+                return Some((Segment { owner: self.owner.clone(), body: Body::Entries(entries) }, removed));
+            }
+            Body::Lanes(lanes) => lanes,
+        };
+
+        let i = Self::lane_for(body, key);
+        let mut lanes = body.clone();
+        let removed = if i > 0 && lanes[i].key() == Some(key) {
+            // The key's tower ends here: fold its segment into the one before it
+            let removed = lanes[i].owner.clone()?;
+            let merged = Segment::merge(&lanes[i - 1], &lanes[i]);
+            lanes.splice(i - 1..=i, [Arc::new(merged)]);
+            removed
+        } else {
+            let (lower, removed) = lanes[i].remove(key)?;
+            lanes[i] = Arc::new(lower);
+            removed
+        };
+        Some((Segment { owner: self.owner.clone(), body: Body::Lanes(lanes) }, removed))
+    }
+
+    /// Join two neighboring segments, dropping the tower that starts `right`
+    fn merge(left: &Self, right: &Self) -> Self {
+        let body = match (&left.body, &right.body) {
+            (Body::Entries(l), Body::Entries(r)) => {
+                Body::Entries(l.iter().chain(r.iter()).cloned().collect())
+            }
+            (Body::Lanes(l), Body::Lanes(r)) => {
+                let (last, front) = l.split_last().expect("segments always have a first lane");
+                let mut lanes = front.to_vec();
+                lanes.push(Arc::new(Segment::merge(last, &r[0])));
+                lanes.extend(r[1..].iter().cloned());
+                Body::Lanes(lanes)
+            }
+            _ => unreachable!("neighboring segments share a level"),
+        };
+        Segment { owner: left.owner.clone(), body }
+    }
+}
+
+/// An immutable skip list whose versions share structure.
+///
+/// Every update returns a new list; the list it was called on keeps its
+/// contents. Clones are O(1) and updates copy O(log n) nodes on average, which
+/// makes it cheap to keep many diverging versions around (undo stacks,
+/// speculative execution).
+///
+/// # Examples
+///
+/// ```
+/// use skiplist_rs::PersistentSkipList;
+///
+/// let v1 = PersistentSkipList::new().insert(1, "one");
+/// let v2 = v1.insert(2, "two");
+///
+/// assert_eq!(v1.get(&2), None);
+/// assert_eq!(v2.get(&2), Some(&"two"));
+/// ```
+pub struct PersistentSkipList<K, V> {
+    root: Arc<Segment<K, V>>,
+    /// Number of levels above level 0 (the tallest tower allowed)
+    max: usize,
+    len: usize,
+    p: f64,
+}
+
+impl<K, V> Clone for PersistentSkipList<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            root: Arc::clone(&self.root),
+            max: self.max,
+            len: self.len,
+            p: self.p,
+        }
+    }
+}
+
+impl<K, V> Default for PersistentSkipList<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> PersistentSkipList<K, V>
+where
+    K: Ord,
+{
+    /// Creates a new empty persistent skip list.
+    ///
+    /// Uses probability 0.5 and starts with 4 levels, like [`SkipList::new`](crate::SkipList::new).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::PersistentSkipList;
+    ///
+    /// let list: PersistentSkipList<i32, String> = PersistentSkipList::new();
+    /// assert!(list.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self::with_params(4, 0.5)
+    }
+
+    /// Creates a new persistent skip list with custom parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_max` - Initial number of levels (will grow automatically)
+    /// * `p` - Probability for level generation (typically 0.25 or 0.5)
+    pub fn with_params(initial_max: usize, p: f64) -> Self {
+        let max = initial_max.max(1);
+        Self {
+            root: Arc::new(Segment::head(max)),
+            max,
+            len: 0,
+            p,
+        }
+    }
+
+    /// Returns the number of elements in this version.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if this version is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Generate a random tower height for a new entry
+    fn random_level(&self) -> usize {
+        level::level_from(&mut None, self.p, self.max)
+    }
+
+    /// Returns a reference to the value for `key` in this version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::PersistentSkipList;
+    ///
+    /// let list = PersistentSkipList::new().insert(1, "one");
+    /// assert_eq!(list.get(&1), Some(&"one"));
+    /// assert_eq!(list.get(&2), None);
+    /// ```
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.root.get(key)
+    }
+
+    /// Returns true if this version contains `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns a new version with `key` set to `value`.
+    ///
+    /// `self` is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::PersistentSkipList;
+    ///
+    /// let old = PersistentSkipList::new().insert(1, "one");
+    /// let new = old.insert(1, "ONE");
+    ///
+    /// assert_eq!(old.get(&1), Some(&"one"));
+    /// assert_eq!(new.get(&1), Some(&"ONE"));
+    /// ```
+    pub fn insert(&self, key: K, value: V) -> Self {
+        let entry = Arc::new((key, value));
+
+        if self.contains_key(&entry.0) {
+            return Self {
+                root: Arc::new(self.root.replace(entry)),
+                ..self.clone()
+            };
+        }
+
+        let len = self.len + 1;
+        let max = self.max.max(level::optimal_levels(len));
+        let mut root = Arc::clone(&self.root);
+        for _ in self.max..max {
+            // Lift the head one level above the old root
+            root = Arc::new(Segment { owner: None, body: Body::Lanes(vec![root]) });
+        }
+
+        let grown = Self { root, max, len, p: self.p };
+        let height = grown.random_level();
+        let (root, split) = grown.root.insert(max, entry, height);
+        debug_assert!(split.is_none(), "towers never reach above the head");
+
+        Self { root: Arc::new(root), ..grown }
+    }
+
+    /// Returns a new version without `key`.
+    ///
+    /// `self` is left unchanged. If `key` is missing the result shares
+    /// everything with `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::PersistentSkipList;
+    ///
+    /// let old = PersistentSkipList::new().insert(1, "one");
+    /// let new = old.remove(&1);
+    ///
+    /// assert!(old.contains_key(&1));
+    /// assert!(!new.contains_key(&1));
+    /// ```
+    pub fn remove(&self, key: &K) -> Self {
+        match self.root.remove(key) {
+            Some((root, _)) => Self {
+                root: Arc::new(root),
+                len: self.len - 1,
+                ..self.clone()
+            },
+            None => self.clone(),
+        }
+    }
+
+    /// Returns true if both lists are the same version (share their root).
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.root, &other.root)
+    }
+
+    /// Returns an iterator over the entries of this version in key order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::PersistentSkipList;
+    ///
+    /// let list = PersistentSkipList::new().insert(2, "two").insert(1, "one");
+    /// let keys: Vec<_> = list.iter().map(|(k, _)| *k).collect();
+    /// assert_eq!(keys, vec![1, 2]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            stack: vec![(&*self.root, 0)],
+            pending: None,
+            remaining: self.len,
+        }
+    }
+}
+
+/// In-order iterator over a [`PersistentSkipList`] version.
+pub struct Iter<'a, K, V> {
+    /// Segments being walked, with the next child or entry index to visit
+    stack: Vec<(&'a Segment<K, V>, usize)>,
+    /// Owner of the segment just entered, yielded before its body
+    pending: Option<&'a (K, V)>,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.pending.take() {
+                self.remaining -= 1;
+                return Some((&entry.0, &entry.1));
+            }
+            let (seg, idx) = self.stack.last_mut()?;
+            let seg: &'a Segment<K, V> = seg;
+            match &seg.body {
+                Body::Entries(entries) => match entries.get(*idx) {
+                    Some(entry) => {
+                        *idx += 1;
+                        self.remaining -= 1;
+                        return Some((&entry.0, &entry.1));
+                    }
+                    None => {
+                        self.stack.pop();
+                    }
+                },
+                Body::Lanes(lanes) => match lanes.get(*idx) {
+                    Some(lane) => {
+                        // The first lane shares its owner with the parent, which
+                        // has already been yielded
+                        if *idx > 0 {
+                            self.pending = lane.owner.as_deref();
+                        }
+                        *idx += 1;
+                        self.stack.push((lane, 0));
+                    }
+                    None => {
+                        self.stack.pop();
+                    }
+                },
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
@@ -0,0 +1,159 @@
+//! Prefix scans and longest-prefix lookups for byte-like keys.
+//!
+//! For keys such as `String` or `Vec<u8>`, every key that starts with a given
+//! prefix sits in one contiguous run of the list, right after where the prefix
+//! itself would go. [`SkipList::prefix_iter`] seeks there with one search and
+//! walks level 0 until a key no longer matches.
+//!
+//! [`SkipList::longest_prefix_match`] answers the routing-table question
+//! "which stored key is the longest prefix of this one". The longest stored
+//! prefix is the largest one, so the lookup searches for the last key not
+//! above the query; if that key isn't a prefix, the query is cut back to
+//! what the two share and the search repeats. Each retry is strictly shorter,
+//! and in practice one or two searches settle it.
+//!
+//! Both compare `key.as_ref()` byte by byte, so they assume the key type's
+//! `Ord` agrees with the byte order of its `AsRef<[u8]>` view, as it does for
+//! `String`, `&str`, `Vec<u8>` and `Box<[u8]>`.
+
+use crate::{NodeRef, SkipList};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Iterator over the entries whose keys start with a prefix, in key order.
+///
+/// Created by [`SkipList::prefix_iter`].
+pub struct PrefixIter<'a, K, V> {
+    /// Next node to yield, if it still matches
+    next: Option<NodeRef<K, V>>,
+    prefix: Vec<u8>,
+    _list: PhantomData<&'a SkipList<K, V>>,
+}
+
+impl<K, V> Iterator for PrefixIter<'_, K, V>
+where
+    K: AsRef<[u8]> + Clone,
+    V: Clone,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next.take()?;
+        let node_ref = node.read().unwrap();
+        let key = node_ref.key.as_ref()?;
+        // Matching keys are contiguous, so the first miss ends the scan
+        if !key.as_ref().starts_with(&self.prefix) {
+            return None;
+        }
+        self.next = node_ref.fwd[0].clone();
+        Some((key.clone(), node_ref.val.clone()?))
+    }
+}
+
+impl<K, V> FusedIterator for PrefixIter<'_, K, V>
+where
+    K: AsRef<[u8]> + Clone,
+    V: Clone,
+{
+}
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord + AsRef<[u8]>,
+{
+    /// Returns an iterator over the entries whose keys start with `prefix`.
+    ///
+    /// Seeking to the first match costs one search; the scan then stops at
+    /// the first key that doesn't match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.insert("acme/alice/1".to_string(), 1);
+    /// skiplist.insert("acme/bob/7".to_string(), 7);
+    /// skiplist.insert("acme/bob/9".to_string(), 9);
+    /// skiplist.insert("acme/bobby/2".to_string(), 2);
+    ///
+    /// let bob: Vec<_> = skiplist.prefix_iter("acme/bob/").map(|(_, v)| v).collect();
+    /// assert_eq!(bob, vec![7, 9]);
+    /// ```
+    pub fn prefix_iter<P>(&self, prefix: P) -> PrefixIter<'_, K, V>
+    where
+        P: AsRef<[u8]>,
+    {
+        let prefix = prefix.as_ref().to_vec();
+        let next = self.last_below_bytes(&prefix, false).read().unwrap().fwd[0].clone();
+        PrefixIter { next, prefix, _list: PhantomData }
+    }
+
+    /// Returns the entry whose key is the longest prefix of `key`, or `None`
+    /// if no stored key is a prefix of it.
+    ///
+    /// A stored key equal to `key` counts as a prefix of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut routes = SkipList::new();
+    /// routes.insert("/".to_string(), "root");
+    /// routes.insert("/api/".to_string(), "api");
+    /// routes.insert("/api/v2/".to_string(), "v2");
+    ///
+    /// assert_eq!(routes.longest_prefix_match("/api/v2/users").map(|(_, v)| v), Some("v2"));
+    /// assert_eq!(routes.longest_prefix_match("/api/v1/users").map(|(_, v)| v), Some("api"));
+    /// assert_eq!(routes.longest_prefix_match("/static").map(|(_, v)| v), Some("root"));
+    /// assert_eq!(routes.longest_prefix_match("static"), None);
+    /// ```
+    pub fn longest_prefix_match<Q>(&self, key: Q) -> Option<(K, V)>
+    where
+        Q: AsRef<[u8]>,
+        K: Clone,
+        V: Clone,
+    {
+        let mut bound = key.as_ref();
+        loop {
+            let node = self.last_below_bytes(bound, true);
+            if Arc::ptr_eq(&node, &self.head) {
+                return None;
+            }
+            let node_ref = node.read().unwrap();
+            let candidate = node_ref.key.as_ref()?;
+            let bytes = candidate.as_ref();
+            if bound.starts_with(bytes) {
+                return Some((candidate.clone(), node_ref.val.clone()?));
+            }
+            // Any longer prefix would have been found, so only the shared
+            // part can still hold one
+            let shared = bytes.iter().zip(bound).take_while(|(a, b)| a == b).count();
+            bound = &bound[..shared];
+        }
+    }
+
+    /// Last node whose key's bytes are below `bytes` (or equal, if
+    /// `inclusive`); the head if there is none
+    fn last_below_bytes(&self, bytes: &[u8], inclusive: bool) -> NodeRef<K, V> {
+        let mut curr = Arc::clone(&self.head);
+        for level in (0..self.max).rev() {
+            loop {
+                let next = curr.read().unwrap().fwd[level].clone();
+                let Some(next) = next else { break };
+                let advance = {
+                    let next_ref = next.read().unwrap();
+                    let key = next_ref.key.as_ref().expect("linked node has a key").as_ref();
+                    if inclusive { key <= bytes } else { key < bytes }
+                };
+                if !advance {
+                    break;
+                }
+                curr = next;
+            }
+        }
+        curr
+    }
+}
@@ -0,0 +1,347 @@
+//! Skip list addressed by position instead of by key.
+//!
+//! A [`SkipSeq`] is a sequence, like a rope: every forward link also records
+//! its *width*, the number of level-0 steps it skips. Summing widths on the
+//! way down finds the element at any index in O(log n) expected, the same way
+//! comparing keys does in a [`SkipList`](crate::SkipList).
+//!
+//! ```text
+//! Level 1: [H] ------3-----> [c] ------3-----> end
+//! Level 0: [H] -1-> [a] -1-> [b] -1-> [c] -1-> [d] -1-> [e] -1-> end
+//! Index:          0        1        2        3        4
+//! ```
+//!
+//! A link running off the end has the width it would have if the end were one
+//! more element, so inserting or removing only adjusts the links on one search
+//! path, and `split_at` and `concat` only re-stitch the links that cross the
+//! cut, each in O(log n) expected with both lengths still exact.
+
+use crate::level;
+use crate::sync::{Arc, RwLock};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+type SeqRef<T> = Arc<RwLock<SeqNode<T>>>;
+
+/// A forward link and how many positions it advances
+struct SeqLink<T> {
+    next: Option<SeqRef<T>>,
+    width: usize,
+}
+
+struct SeqNode<T> {
+    /// `None` only for the head
+    value: Option<T>,
+    links: Vec<SeqLink<T>>,
+}
+
+/// A sequence stored in a skip list, indexed by position.
+///
+/// Inserting, removing or reading at an index, splitting and concatenating
+/// are all O(log n) expected, which suits editor buffers and other long
+/// sequences edited in the middle.
+///
+/// # Examples
+///
+/// ```
+/// use skiplist_rs::SkipSeq;
+///
+/// let mut text: SkipSeq<char> = "held".chars().collect();
+/// text.insert_at(3, 'l');
+/// text.insert_at(4, 'o');
+/// assert_eq!(text.iter().collect::<String>(), "hellod");
+///
+/// let tail = text.split_at(5);
+/// assert_eq!(tail.get(0), Some('d'));
+/// text.concat(", world".chars().collect());
+/// assert_eq!(text.iter().collect::<String>(), "hello, world");
+/// ```
+pub struct SkipSeq<T> {
+    head: SeqRef<T>,
+    max: usize,
+    len: usize,
+    p: f64,
+    /// Only set once [`seed`](Self::seed) is called
+    rng: Option<StdRng>,
+}
+
+impl<T> Default for SkipSeq<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SkipSeq<T> {
+    /// Creates an empty sequence with probability 0.5 and 4 initial levels.
+    pub fn new() -> Self {
+        Self::with_params(4, 0.5)
+    }
+
+    /// Creates an empty sequence with `initial_max` levels to start with and
+    /// probability `p` of a tower growing another level.
+    pub fn with_params(initial_max: usize, p: f64) -> Self {
+        let max = initial_max.max(1);
+        let links = (0..max).map(|_| SeqLink { next: None, width: 1 }).collect();
+        SkipSeq {
+            head: Arc::new(RwLock::new(SeqNode { value: None, links })),
+            max,
+            len: 0,
+            p,
+            rng: None,
+        }
+    }
+
+    /// Makes tower heights repeatable: sequences seeded alike and given the
+    /// same edits end up with the same links.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = Some(StdRng::seed_from_u64(seed));
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Add head levels until there are `levels`; each new link spans the
+    /// whole sequence
+    fn grow(&mut self, levels: usize) {
+        let mut head = self.head.write().unwrap();
+        while self.max < levels {
+            head.links.push(SeqLink { next: None, width: self.len + 1 });
+            self.max += 1;
+        }
+    }
+
+    /// Grow the head so towers can reach about log2(len) + 2
+    fn resize(&mut self) {
+        let optimal = level::optimal_levels(self.len);
+        self.grow(optimal);
+    }
+
+    /// Rightmost node before `position` at every level, with its position
+    ///
+    /// The head is position 0 and the element at index `i` is position `i + 1`.
+    fn predecessors(&self, position: usize) -> Vec<(SeqRef<T>, usize)> {
+        let mut update = Vec::with_capacity(self.max);
+        let mut curr = Arc::clone(&self.head);
+        let mut pos = 0;
+        for level in (0..self.max).rev() {
+            loop {
+                let next = {
+                    let node = curr.read().unwrap();
+                    let link = &node.links[level];
+                    link.next.as_ref().filter(|_| pos + link.width < position).map(|next| (Arc::clone(next), link.width))
+                };
+                match next {
+                    Some((next, width)) => {
+                        curr = next;
+                        pos += width;
+                    }
+                    None => break,
+                }
+            }
+            update.push((Arc::clone(&curr), pos));
+        }
+        update.reverse();
+        update
+    }
+
+    /// Inserts `value` so that it ends up at `index`, shifting everything
+    /// after it one place to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert_at(&mut self, index: usize, value: T) {
+        assert!(index <= self.len, "insertion index (is {}) should be <= len (is {})", index, self.len);
+        self.resize();
+
+        let update = self.predecessors(index + 1);
+        let height = level::level_from(&mut self.rng, self.p, self.max);
+        let node = Arc::new(RwLock::new(SeqNode { value: Some(value), links: Vec::with_capacity(height) }));
+        {
+            let mut new_node = node.write().unwrap();
+            for (level, (pred, pred_pos)) in update.iter().enumerate() {
+                let mut pred = pred.write().unwrap();
+                let link = &mut pred.links[level];
+                if level < height {
+                    // Everything past the new node moves one place right
+                    new_node.links.push(SeqLink { next: link.next.take(), width: pred_pos + link.width - index });
+                    *link = SeqLink { next: Some(Arc::clone(&node)), width: index + 1 - pred_pos };
+                } else {
+                    link.width += 1;
+                }
+            }
+        }
+        self.len += 1;
+    }
+
+    /// Appends `value` to the end of the sequence.
+    pub fn push(&mut self, value: T) {
+        self.insert_at(self.len, value);
+    }
+
+    /// Removes and returns the element at `index`, shifting everything after
+    /// it one place to the left, or returns `None` if `index` is out of
+    /// bounds.
+    pub fn remove_at(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        let update = self.predecessors(index + 1);
+        let node = update[0].0.read().unwrap().links[0].next.clone().expect("index is in bounds");
+        let mut removed = node.write().unwrap();
+        let height = removed.links.len();
+        for (level, (pred, _)) in update.iter().enumerate() {
+            let mut pred = pred.write().unwrap();
+            let link = &mut pred.links[level];
+            if level < height {
+                let skipped = &mut removed.links[level];
+                *link = SeqLink { next: skipped.next.take(), width: link.width + skipped.width - 1 };
+            } else {
+                link.width -= 1;
+            }
+        }
+        self.len -= 1;
+        removed.value.take()
+    }
+
+    /// Returns a clone of the element at `index`, or `None` if it is out of
+    /// bounds.
+    pub fn get(&self, index: usize) -> Option<T>
+    where
+        T: Clone,
+    {
+        if index >= self.len {
+            return None;
+        }
+        let (pred, _) = &self.predecessors(index + 1)[0];
+        let node = pred.read().unwrap().links[0].next.clone()?;
+        let value = node.read().unwrap().value.clone();
+        value
+    }
+
+    /// Splits the sequence in two at `index`.
+    ///
+    /// Returns a new sequence holding the elements from `index` on; `self`
+    /// keeps the first `index`. Only the links crossing the cut change, so
+    /// this is O(log n) expected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn split_at(&mut self, index: usize) -> Self {
+        assert!(index <= self.len, "split index (is {}) should be <= len (is {})", index, self.len);
+        let update = self.predecessors(index + 1);
+        let mut other = Self::with_params(self.max, self.p);
+        {
+            let mut other_head = other.head.write().unwrap();
+            for (level, (pred, pred_pos)) in update.iter().enumerate() {
+                let mut pred = pred.write().unwrap();
+                let link = &mut pred.links[level];
+                // Positions on the right shift down by `index`
+                other_head.links[level] = SeqLink { next: link.next.take(), width: pred_pos + link.width - index };
+                link.width = index + 1 - pred_pos;
+            }
+        }
+        other.len = self.len - index;
+        self.len = index;
+        other
+    }
+
+    /// Appends every element of `other` to the end of `self`.
+    ///
+    /// The last tower at each level is linked to `other`'s first, so this is
+    /// O(log n) expected.
+    pub fn concat(&mut self, mut other: Self) {
+        let levels = self.max.max(other.max);
+        self.grow(levels);
+        other.grow(levels);
+
+        let update = self.predecessors(self.len + 1);
+        {
+            let mut other_head = other.head.write().unwrap();
+            for (level, (pred, pred_pos)) in update.iter().enumerate() {
+                let mut pred = pred.write().unwrap();
+                let right = &mut other_head.links[level];
+                // Positions in `other` shift up by `self.len`
+                pred.links[level] = SeqLink { next: right.next.take(), width: self.len + right.width - pred_pos };
+            }
+        }
+        self.len += other.len;
+        other.len = 0;
+        self.resize();
+    }
+
+    /// Returns an iterator over clones of the elements in order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let front = self.head.read().unwrap().links[0].next.clone();
+        Iter { front, remaining: self.len, _seq: PhantomData }
+    }
+}
+
+impl<T> Drop for SkipSeq<T> {
+    /// Unlink nodes one at a time so a long sequence doesn't drop recursively
+    fn drop(&mut self) {
+        let mut next = {
+            let mut head = self.head.write().unwrap();
+            let first = head.links.first_mut().and_then(|link| link.next.take());
+            head.links.clear();
+            first
+        };
+        while let Some(node) = next {
+            let mut node = node.write().unwrap();
+            next = node.links.first_mut().and_then(|link| link.next.take());
+            node.links.clear();
+        }
+    }
+}
+
+impl<T> FromIterator<T> for SkipSeq<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut seq = SkipSeq::new();
+        for value in iter {
+            seq.push(value);
+        }
+        seq
+    }
+}
+
+/// Iterator over the elements of a [`SkipSeq`] in order.
+///
+/// Created by [`SkipSeq::iter`].
+pub struct Iter<'a, T> {
+    front: Option<SeqRef<T>>,
+    remaining: usize,
+    _seq: PhantomData<&'a SkipSeq<T>>,
+}
+
+impl<T> Iterator for Iter<'_, T>
+where
+    T: Clone,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node = self.front.take()?;
+        let node = node.read().unwrap();
+        self.front = node.links[0].next.clone();
+        self.remaining -= 1;
+        node.value.clone()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> where T: Clone {}
+
+impl<T> FusedIterator for Iter<'_, T> where T: Clone {}
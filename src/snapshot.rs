@@ -0,0 +1,336 @@
+//! Binary snapshots of a [`SkipList`], tower heights included.
+//!
+//! [`SkipList::to_snapshot`] writes the list's parameters and every entry in
+//! key order along with the height of its tower, so
+//! [`SkipList::from_snapshot`] rebuilds a list with exactly the same shape.
+//! That makes a snapshot a way to hand someone the precise structure a bug
+//! showed up in.
+//!
+//! ```text
+//! magic    b"SKPL"
+//! version  u8
+//! p        f64, little-endian
+//! levels   u8, head levels
+//! len      u64, little-endian
+//! entries  len x (height u8, key, value)
+//! ```
+//!
+//! Keys and values are encoded by [`SnapshotValue`]: integers as fixed-width
+//! little-endian, strings and byte vectors as a `u32` length followed by the
+//! bytes.
+//!
+//! [`SkipList::write_snapshot`] and [`SkipList::read_snapshot`] stream the
+//! same format through `io::Write` and `io::Read`, so a snapshot larger than
+//! memory never has to be held as one buffer next to the list it describes.
+//!
+//! Loading treats its input as untrusted. Length-prefixed fields are read
+//! incrementally and buffers only grow as bytes actually arrive, so malformed
+//! input is reported as a [`SnapshotError`] instead of panicking or
+//! allocating more than the input justifies.
+
+use crate::{NodeRef, SkipList};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+
+const MAGIC: &[u8; 4] = b"SKPL";
+const VERSION: u8 = 1;
+/// Most levels a snapshot may declare
+const MAX_LEVELS: u8 = 64;
+
+/// Error returned by [`SkipList::from_snapshot`] for malformed input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The input does not start with the snapshot magic bytes
+    BadMagic,
+    /// The snapshot was written by an unknown format version
+    UnsupportedVersion(u8),
+    /// The input ended in the middle of a field
+    Truncated,
+    /// The level probability is not a number between 0 and 1
+    InvalidProbability,
+    /// The head level count is zero or above the supported maximum
+    InvalidLevels(u8),
+    /// The entry count is larger than the input could possibly hold
+    InvalidLength(u64),
+    /// An entry's tower is empty or taller than the head
+    InvalidHeight { index: usize, height: u8 },
+    /// A key or value could not be decoded (for example, a non-UTF-8 string)
+    InvalidValue,
+    /// An entry's key is not greater than the key before it
+    Unsorted { index: usize },
+    /// Bytes were left over after the last entry
+    TrailingBytes,
+    /// Reading the snapshot failed
+    Io(io::ErrorKind),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::BadMagic => write!(f, "not a skip list snapshot"),
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "unsupported snapshot version {}", version)
+            }
+            SnapshotError::Truncated => write!(f, "snapshot ends unexpectedly"),
+            SnapshotError::InvalidProbability => write!(f, "level probability is not between 0 and 1"),
+            SnapshotError::InvalidLevels(levels) => write!(f, "invalid head level count {}", levels),
+            SnapshotError::InvalidLength(len) => {
+                write!(f, "entry count {} does not fit in the snapshot", len)
+            }
+            SnapshotError::InvalidHeight { index, height } => {
+                write!(f, "entry {} has invalid tower height {}", index, height)
+            }
+            SnapshotError::InvalidValue => write!(f, "a key or value could not be decoded"),
+            SnapshotError::Unsorted { index } => {
+                write!(f, "entry {} is not greater than its predecessor", index)
+            }
+            SnapshotError::TrailingBytes => write!(f, "unexpected bytes after the last entry"),
+            SnapshotError::Io(kind) => write!(f, "failed to read snapshot: {}", kind),
+        }
+    }
+}
+
+impl Error for SnapshotError {}
+
+/// Map a read failure to a snapshot error
+fn read_error(err: io::Error) -> SnapshotError {
+    match err.kind() {
+        io::ErrorKind::UnexpectedEof => SnapshotError::Truncated,
+        kind => SnapshotError::Io(kind),
+    }
+}
+
+/// Read a fixed-size array from the front of `input`
+fn read_array<R: Read, const N: usize>(input: &mut R) -> Result<[u8; N], SnapshotError> {
+    let mut array = [0; N];
+    input.read_exact(&mut array).map_err(read_error)?;
+    Ok(array)
+}
+
+/// Read `len` bytes, growing the buffer only as bytes actually arrive
+fn read_bytes<R: Read>(input: &mut R, len: usize) -> Result<Vec<u8>, SnapshotError> {
+    let mut bytes = Vec::new();
+    input.take(len as u64).read_to_end(&mut bytes).map_err(read_error)?;
+    if bytes.len() < len {
+        return Err(SnapshotError::Truncated);
+    }
+    Ok(bytes)
+}
+
+/// A key or value type that can be stored in a snapshot.
+///
+/// Implemented for the integer types, `String` and `Vec<u8>`.
+pub trait SnapshotValue: Sized {
+    /// Appends the encoded value to `out`.
+    fn encode(&self, out: &mut Vec<u8>);
+
+    /// Decodes a value from the front of `input`, advancing past it.
+    ///
+    /// Implementations must not allocate more than the bytes actually read
+    /// from `input` can account for.
+    fn decode<R: Read>(input: &mut R) -> Result<Self, SnapshotError>;
+}
+
+macro_rules! snapshot_int {
+    ($($ty:ty),*) => {
+        $(
+            impl SnapshotValue for $ty {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn decode<R: Read>(input: &mut R) -> Result<Self, SnapshotError> {
+                    Ok(<$ty>::from_le_bytes(read_array(input)?))
+                }
+            }
+        )*
+    };
+}
+
+snapshot_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl SnapshotValue for Vec<u8> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        let len = u32::try_from(self.len()).expect("snapshot fields are limited to 4 GiB");
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(self);
+    }
+
+    fn decode<R: Read>(input: &mut R) -> Result<Self, SnapshotError> {
+        let len = u32::from_le_bytes(read_array(input)?) as usize;
+        read_bytes(input, len)
+    }
+}
+
+impl SnapshotValue for String {
+    fn encode(&self, out: &mut Vec<u8>) {
+        let len = u32::try_from(self.len()).expect("snapshot fields are limited to 4 GiB");
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(self.as_bytes());
+    }
+
+    fn decode<R: Read>(input: &mut R) -> Result<Self, SnapshotError> {
+        let bytes = Vec::<u8>::decode(input)?;
+        String::from_utf8(bytes).map_err(|_| SnapshotError::InvalidValue)
+    }
+}
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord + SnapshotValue,
+    V: SnapshotValue,
+{
+    /// Encodes the list, tower heights included, as a snapshot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the list has more than 64 levels (only possible when it
+    /// was created with that many), or if a string or byte key or value is
+    /// 4 GiB or longer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// for i in 0..100u32 {
+    ///     skiplist.insert(i, i.to_string());
+    /// }
+    ///
+    /// let bytes = skiplist.to_snapshot();
+    /// let copy: SkipList<u32, String> = SkipList::from_snapshot(&bytes).unwrap();
+    /// assert_eq!(copy.len(), 100);
+    /// assert_eq!(copy.to_ascii(), skiplist.to_ascii());
+    /// ```
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.write_snapshot(&mut out).expect("writing to a Vec cannot fail");
+        out
+    }
+
+    /// Streams the list's snapshot to `writer` one entry at a time.
+    ///
+    /// Produces the same bytes as [`to_snapshot`](Self::to_snapshot) without
+    /// holding the whole encoding in memory. The writer is not buffered here;
+    /// wrap files in a `BufWriter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`to_snapshot`](Self::to_snapshot).
+    pub fn write_snapshot<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(MAGIC);
+        buf.push(VERSION);
+        buf.extend_from_slice(&self.p.to_le_bytes());
+        let levels = u8::try_from(self.max).ok().filter(|&levels| levels <= MAX_LEVELS);
+        buf.push(levels.expect("too many levels for a snapshot"));
+        buf.extend_from_slice(&(self.len as u64).to_le_bytes());
+        writer.write_all(&buf)?;
+
+        let mut next = self.head.read().unwrap().fwd[0].clone();
+        while let Some(node) = next {
+            let node_ref = node.read().unwrap();
+            if let (Some(key), Some(val)) = (&node_ref.key, &node_ref.val) {
+                buf.clear();
+                buf.push(node_ref.height() as u8);
+                key.encode(&mut buf);
+                val.encode(&mut buf);
+                writer.write_all(&buf)?;
+            }
+            next = node_ref.fwd[0].clone();
+        }
+        Ok(())
+    }
+
+    /// Rebuilds a list from a snapshot made by [`to_snapshot`](Self::to_snapshot).
+    ///
+    /// The input is validated as it is read: keys must be strictly increasing
+    /// and every tower must fit under the head. Loading never panics on
+    /// malformed input and allocates in proportion to the input's size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::{SkipList, SnapshotError};
+    ///
+    /// let result = SkipList::<u32, u32>::from_snapshot(b"not a snapshot");
+    /// assert_eq!(result.err(), Some(SnapshotError::BadMagic));
+    /// ```
+    pub fn from_snapshot(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        // Every entry takes at least its height byte
+        Self::decode_snapshot(&mut &bytes[..], bytes.len() as u64)
+    }
+
+    /// Rebuilds a list from a snapshot streamed from `reader`.
+    ///
+    /// Entries are linked in as they are decoded, so the encoded snapshot is
+    /// never held in memory. The reader is not buffered here; wrap files in a
+    /// `BufReader`. Errors from the reader are reported as
+    /// [`SnapshotError::Io`].
+    pub fn read_snapshot<R: Read>(mut reader: R) -> Result<Self, SnapshotError> {
+        Self::decode_snapshot(&mut reader, u64::MAX)
+    }
+
+    /// Decode a snapshot that claims at most `max_len` entries
+    fn decode_snapshot<R: Read>(input: &mut R, max_len: u64) -> Result<Self, SnapshotError> {
+        if &read_array::<_, 4>(input)? != MAGIC {
+            return Err(SnapshotError::BadMagic);
+        }
+        let [version] = read_array(input)?;
+        if version != VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        let p = f64::from_le_bytes(read_array(input)?);
+        if !(0.0..=1.0).contains(&p) {
+            return Err(SnapshotError::InvalidProbability);
+        }
+        let [levels] = read_array(input)?;
+        if levels == 0 || levels > MAX_LEVELS {
+            return Err(SnapshotError::InvalidLevels(levels));
+        }
+        let len = u64::from_le_bytes(read_array(input)?);
+        if len > max_len {
+            return Err(SnapshotError::InvalidLength(len));
+        }
+
+        let mut list = Self::with_params(levels as usize, p);
+        // Last node at each level, which the next entry is linked after
+        let mut last: Vec<NodeRef<K, V>> = vec![Arc::clone(&list.head); list.max];
+
+        for index in 0..len {
+            let index = index as usize;
+            let [height] = read_array(input)?;
+            if height == 0 || height as usize > list.max {
+                return Err(SnapshotError::InvalidHeight { index, height });
+            }
+            let key = K::decode(input)?;
+            let val = V::decode(input)?;
+
+            let in_order = match &last[0].read().unwrap().key {
+                Some(prev) => prev < &key,
+                None => true,
+            };
+            if !in_order {
+                return Err(SnapshotError::Unsorted { index });
+            }
+
+            let node = list.link_after(&last[..height as usize], key, val);
+            for pred in last.iter_mut().take(height as usize) {
+                *pred = Arc::clone(&node);
+            }
+            // Linking may have grown the head
+            while last.len() < list.max {
+                last.push(Arc::clone(&list.head));
+            }
+        }
+
+        match input.read(&mut [0]) {
+            Ok(0) => Ok(list),
+            Ok(_) => Err(SnapshotError::TrailingBytes),
+            Err(err) => Err(read_error(err)),
+        }
+    }
+}
@@ -0,0 +1,267 @@
+//! Partitioning and merging lists: `split_off` and `append`.
+//!
+//! Cutting a skip list at a key only touches the predecessors found by one
+//! search: at every level the link that crosses the boundary is moved over to
+//! the new list's head. Concatenating two lists whose keys don't overlap is the
+//! same operation in reverse. Only interleaved key ranges need a full merge.
+
+use crate::{NodeRef, SkipList};
+use std::cmp::Ordering;
+use std::mem;
+use std::sync::{Arc, Weak};
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord,
+{
+    /// Splits the list in two at `key`.
+    ///
+    /// Returns a new list holding every entry with a key greater than or equal
+    /// to `key`; `self` keeps the rest.
+    ///
+    /// Cutting the links costs one search, O(log n) expected. Keeping both
+    /// lengths exact also needs a count of one side, which walks both halves in
+    /// step from the cut and stops at the end of the shorter one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut left = SkipList::new();
+    /// for i in 0..10 {
+    ///     left.insert(i, i * 10);
+    /// }
+    ///
+    /// let right = left.split_off(&6);
+    /// assert_eq!(left.len(), 6);
+    /// assert_eq!(right.len(), 4);
+    /// assert_eq!(right.get(&6), Some(60));
+    /// assert_eq!(left.get(&6), None);
+    /// ```
+    pub fn split_off(&mut self, key: &K) -> Self {
+        let update = self.find_predecessors(key);
+        let mut other = Self::with_params(self.max, self.p);
+
+        let first = {
+            let mut other_head = other.head.write().unwrap();
+            for (level, pred) in update.iter().enumerate() {
+                other_head.fwd[level] = pred.write().unwrap().fwd[level].take();
+            }
+            other_head.fwd[0].clone()
+        };
+        let Some(first) = first else {
+            return other;
+        };
+        first.write().unwrap().back = Some(Arc::downgrade(&other.head));
+
+        let left_last = Arc::clone(&update[0]);
+        other.tail = self.tail.take();
+        if !Arc::ptr_eq(&left_last, &self.head) {
+            self.tail = Some(Arc::downgrade(&left_last));
+        }
+
+        // Count the shorter half by walking away from the cut in both directions
+        let not_head = |node: &NodeRef<K, V>| !Arc::ptr_eq(node, &self.head);
+        let mut left = Some(left_last).filter(not_head);
+        let mut right = Some(first);
+        let mut steps = 0;
+        let moved = loop {
+            match (&left, &right) {
+                (_, None) => break steps,
+                (None, _) => break self.len - steps,
+                (Some(l), Some(r)) => {
+                    let next_left = l.read().unwrap().back.as_ref().and_then(Weak::upgrade);
+                    let next_right = r.read().unwrap().fwd[0].clone();
+                    left = next_left.filter(not_head);
+                    right = next_right;
+                    steps += 1;
+                }
+            }
+        };
+
+        other.len = moved;
+        self.len -= moved;
+        self.epoch += 1;
+        self.recount_usage();
+        other
+    }
+
+    /// Moves every entry of `other` into `self`, leaving `other` empty.
+    ///
+    /// When every key in `other` is greater than every key in `self` (or the
+    /// other way around) the lists are concatenated in O(log n) expected time.
+    /// Otherwise they are merged in one O(n + m) pass. On equal keys the value
+    /// from `other` wins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut a = SkipList::new();
+    /// a.insert(1, "one");
+    /// a.insert(3, "three");
+    ///
+    /// let mut b = SkipList::new();
+    /// b.insert(2, "two");
+    /// b.insert(3, "THREE");
+    ///
+    /// a.append(&mut b);
+    /// assert_eq!(a.len(), 3);
+    /// assert_eq!(a.get(&3), Some("THREE"));
+    /// assert!(b.is_empty());
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            self.swap_entries(other);
+        } else {
+            self.join(other);
+        }
+        // Entries moved without going through link_after or unlink
+        self.epoch += 1;
+        other.epoch += 1;
+        self.recount_usage();
+        other.recount_usage();
+        self.evict_to_capacity();
+    }
+
+    /// Move the entries of `other`, which must not be empty, into `self`,
+    /// which must not be empty either
+    fn join(&mut self, other: &mut Self) {
+
+        let max = self.max.max(other.max);
+        self.grow(max);
+        other.grow(max);
+
+        let self_last = self.last_node();
+        let other_first = other.head.read().unwrap().fwd[0].clone().unwrap();
+        let other_last = other.last_node();
+        let self_first = self.head.read().unwrap().fwd[0].clone().unwrap();
+
+        if self_last.read().unwrap().key < other_first.read().unwrap().key {
+            self.concat(other);
+        } else if other_last.read().unwrap().key < self_first.read().unwrap().key {
+            self.swap_entries(other);
+            self.concat(other);
+        } else {
+            self.merge(other);
+        }
+    }
+
+    /// Exchange the entries of two lists, leaving each list's metrics, level
+    /// probability and generator in place
+    fn swap_entries(&mut self, other: &mut Self) {
+        mem::swap(&mut self.head, &mut other.head);
+        mem::swap(&mut self.tail, &mut other.tail);
+        mem::swap(&mut self.max, &mut other.max);
+        mem::swap(&mut self.len, &mut other.len);
+    }
+
+    /// Rightmost node at every level (the head where a level is empty)
+    fn last_per_level(&self) -> Vec<NodeRef<K, V>> {
+        let mut last = Vec::with_capacity(self.max);
+        let mut curr = Arc::clone(&self.head);
+
+        for level in (0..self.max).rev() {
+            loop {
+                let next = curr.read().unwrap().fwd[level].clone();
+                match next {
+                    Some(node) => curr = node,
+                    None => break,
+                }
+            }
+            last.push(Arc::clone(&curr));
+        }
+
+        last.reverse();
+        last
+    }
+
+    /// Hang `other` off the end of `self`; every key in `other` must be larger
+    fn concat(&mut self, other: &mut Self) {
+        let last = self.last_per_level();
+
+        {
+            let mut other_head = other.head.write().unwrap();
+            for (level, pred) in last.iter().enumerate() {
+                pred.write().unwrap().fwd[level] = other_head.fwd[level].take();
+            }
+        }
+        if let Some(first) = &last[0].read().unwrap().fwd[0] {
+            first.write().unwrap().back = Some(Arc::downgrade(&last[0]));
+        }
+
+        self.tail = other.tail.take();
+        self.len += mem::take(&mut other.len);
+        self.resize();
+    }
+
+    /// Interleave the nodes of both lists and relink every level in one pass
+    fn merge(&mut self, other: &mut Self) {
+        let ours = Self::detach(&self.head);
+        let theirs = Self::detach(&other.head);
+        other.tail = None;
+        other.len = 0;
+
+        let mut merged = Vec::with_capacity(ours.len() + theirs.len());
+        let mut ours = ours.into_iter().peekable();
+        let mut theirs = theirs.into_iter().peekable();
+        loop {
+            let order = match (ours.peek(), theirs.peek()) {
+                (Some(a), Some(b)) => a.read().unwrap().key.cmp(&b.read().unwrap().key),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            match order {
+                Ordering::Less => merged.extend(ours.next()),
+                Ordering::Greater => merged.extend(theirs.next()),
+                Ordering::Equal => {
+                    ours.next();
+                    merged.extend(theirs.next());
+                }
+            }
+        }
+
+        self.relink(merged);
+    }
+
+    /// Unhook every node from `head`, returning them in level-0 order
+    fn detach(head: &NodeRef<K, V>) -> Vec<NodeRef<K, V>> {
+        let mut nodes = Vec::new();
+        let mut next = head.read().unwrap().fwd[0].clone();
+        for link in head.write().unwrap().fwd.iter_mut() {
+            *link = None;
+        }
+
+        while let Some(node) = next {
+            next = node.read().unwrap().fwd[0].clone();
+            nodes.push(node);
+        }
+        nodes
+    }
+
+    /// Rebuild every level from `nodes`, which must be sorted and unique
+    fn relink(&mut self, nodes: Vec<NodeRef<K, V>>) {
+        let mut last: Vec<NodeRef<K, V>> = vec![Arc::clone(&self.head); self.max];
+
+        for node in &nodes {
+            let mut node_ref = node.write().unwrap();
+            node_ref.back = Some(Arc::downgrade(&last[0]));
+            let height = node_ref.height();
+            for (level, pred) in last.iter_mut().enumerate().take(height) {
+                node_ref.fwd[level] = None;
+                pred.write().unwrap().fwd[level] = Some(Arc::clone(node));
+                *pred = Arc::clone(node);
+            }
+        }
+
+        self.len = nodes.len();
+        self.tail = nodes.last().map(Arc::downgrade);
+        self.resize();
+    }
+}
@@ -0,0 +1,152 @@
+//! Structural statistics for a [`SkipList`].
+//!
+//! The README's table of levels and expected comparisons per element count is
+//! what the theory predicts; [`SkipList::stats`] measures the same things on a
+//! real list so the two can be compared.
+
+use crate::sync::RwLock;
+use crate::{Link, Node, SearchTrace, SkipList};
+use std::fmt;
+use std::mem;
+
+/// Number of keys looked up to measure search cost
+const SAMPLE_LOOKUPS: usize = 64;
+
+/// A snapshot of a skip list's shape, returned by [`SkipList::stats`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkipListStats {
+    /// Number of entries
+    pub len: usize,
+    /// Number of levels the head currently has
+    pub max_level: usize,
+    /// Number of levels the list would pick for its current length
+    pub optimal_levels: usize,
+    /// Number of nodes linked into each level, from level 0 up
+    pub nodes_per_level: Vec<usize>,
+    /// Mean tower height over all entries
+    pub avg_height: f64,
+    /// Tallest tower among the entries
+    pub max_height: usize,
+    /// Bytes allocated for nodes (head included) and their `fwd` vectors.
+    ///
+    /// Heap memory owned by keys and values themselves is not counted.
+    pub node_bytes: usize,
+    /// Number of lookups used to measure search cost
+    pub sampled_lookups: usize,
+    /// Mean key comparisons per sampled lookup
+    pub avg_comparisons: f64,
+    /// Most key comparisons any sampled lookup needed
+    pub max_comparisons: usize,
+}
+
+impl fmt::Display for SkipListStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Elements:        {}", self.len)?;
+        writeln!(f, "Levels:          {} (optimal {})", self.max_level, self.optimal_levels)?;
+        writeln!(f, "Tower height:    avg {:.2}, max {}", self.avg_height, self.max_height)?;
+        writeln!(f, "Node memory:     {} bytes", self.node_bytes)?;
+        writeln!(
+            f,
+            "Comparisons:     avg {:.2}, max {} ({} lookups)",
+            self.avg_comparisons, self.max_comparisons, self.sampled_lookups
+        )?;
+        for (level, count) in self.nodes_per_level.iter().enumerate().rev() {
+            writeln!(f, "Level {:>2}:        {} nodes", level, count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Bytes taken by one node allocation and its forward pointers
+fn node_size<K, V>(node: &Node<K, V>) -> usize {
+    // An `Arc` allocation carries a strong and a weak count before the value
+    2 * mem::size_of::<usize>()
+        + mem::size_of::<RwLock<Node<K, V>>>()
+        + node.fwd.capacity() * mem::size_of::<Link<K, V>>()
+}
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord,
+{
+    /// Measures the current structure of the list.
+    ///
+    /// Walks every node once and then looks up an evenly spaced sample of
+    /// keys, counting the key comparisons each lookup makes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// for i in 0..1000 {
+    ///     skiplist.insert(i, i);
+    /// }
+    ///
+    /// let stats = skiplist.stats();
+    /// assert_eq!(stats.len, 1000);
+    /// assert_eq!(stats.nodes_per_level[0], 1000);
+    /// assert!(stats.avg_comparisons < 100.0);
+    /// ```
+    pub fn stats(&self) -> SkipListStats
+    where
+        K: Clone,
+    {
+        let mut nodes_per_level = vec![0; self.max];
+        let mut node_bytes = node_size(&self.head.read().unwrap());
+        let mut total_height = 0;
+        let mut max_height = 0;
+
+        let stride = (self.len / SAMPLE_LOOKUPS).max(1);
+        let mut samples = Vec::with_capacity(SAMPLE_LOOKUPS.min(self.len));
+
+        let mut next = self.head.read().unwrap().fwd[0].clone();
+        let mut index = 0;
+        while let Some(node) = next {
+            let node_ref = node.read().unwrap();
+            let height = node_ref.height();
+            for count in nodes_per_level.iter_mut().take(height) {
+                *count += 1;
+            }
+            total_height += height;
+            max_height = max_height.max(height);
+            node_bytes += node_size(&node_ref);
+
+            if index % stride == 0 && samples.len() < SAMPLE_LOOKUPS {
+                samples.extend(node_ref.key.clone());
+            }
+            index += 1;
+            next = node_ref.fwd[0].clone();
+        }
+
+        let costs: Vec<usize> = samples.iter().map(|key| self.search_cost(key)).collect();
+        let avg = |total: usize, count: usize| {
+            if count == 0 {
+                0.0
+            } else {
+                total as f64 / count as f64
+            }
+        };
+
+        SkipListStats {
+            len: self.len,
+            max_level: self.max,
+            optimal_levels: crate::level::optimal_levels(self.len),
+            nodes_per_level,
+            avg_height: avg(total_height, self.len),
+            max_height,
+            node_bytes,
+            sampled_lookups: costs.len(),
+            avg_comparisons: avg(costs.iter().sum(), costs.len()),
+            max_comparisons: costs.iter().copied().max().unwrap_or(0),
+        }
+    }
+
+    /// Count the key comparisons a lookup for `key` makes
+    fn search_cost(&self, key: &K) -> usize {
+        let mut trace = SearchTrace::default();
+        self.find_node_observed(key, &mut trace);
+        trace.comparisons()
+    }
+}
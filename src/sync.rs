@@ -0,0 +1,16 @@
+//! Synchronization primitives used for the nodes.
+//!
+//! Built with `--cfg loom`, the node locks come from [`loom`] so the model
+//! tests in `tests/loom_tests.rs` can explore every interleaving of lock
+//! acquisitions. Reference counting stays on `std`: loom has no `Weak`, and
+//! the counts are never a synchronization point in this crate.
+//!
+//! [`loom`]: https://docs.rs/loom
+
+pub(crate) use std::sync::{Arc, Weak};
+
+#[cfg(loom)]
+pub(crate) use loom::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+#[cfg(not(loom))]
+pub(crate) use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
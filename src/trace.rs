@@ -0,0 +1,181 @@
+//! Search-path tracing.
+//!
+//! Every search in [`SkipList`] reports what it does to a [`SearchObserver`]:
+//! when it moves down to a level, each lock it takes, each key comparison and
+//! each step forward along a level. The plain `get` and `insert` use the unit
+//! observer `()`, whose methods are empty and compile away.
+//!
+//! [`SearchTrace`] is an observer that tallies those events per level, which
+//! is what [`SkipList::get_traced`] returns. Other observers can forward the
+//! events straight into a metrics system.
+
+use crate::SkipList;
+
+/// Receives the events of a single search.
+///
+/// All methods do nothing by default, so implementors only override what
+/// they care about. Levels are numbered from 0 (the bottom level) up.
+pub trait SearchObserver {
+    /// The search starts working on `level` (called once per level, top down).
+    fn on_descend(&mut self, _level: usize) {}
+
+    /// The search moves forward one node along `level`.
+    fn on_step(&mut self, _level: usize) {}
+
+    /// The search compares the target key with a node's key on `level`.
+    fn on_compare(&mut self, _level: usize) {}
+
+    /// The search takes a node's read lock while on `level`.
+    fn on_lock(&mut self, _level: usize) {}
+}
+
+/// The observer that ignores every event.
+impl SearchObserver for () {}
+
+/// What a search did on one level.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LevelTrace {
+    /// The level these counts belong to
+    pub level: usize,
+    /// Nodes moved past along this level
+    pub steps: usize,
+    /// Key comparisons made on this level
+    pub comparisons: usize,
+    /// Node locks taken on this level
+    pub locks: usize,
+}
+
+/// Per-level tally of a search, built by observing it.
+///
+/// # Examples
+///
+/// ```
+/// use skiplist_rs::SkipList;
+///
+/// let mut skiplist = SkipList::new();
+/// for i in 0..100 {
+///     skiplist.insert(i, i);
+/// }
+///
+/// let (value, trace) = skiplist.get_traced(&42);
+/// assert_eq!(value, Some(42));
+/// assert!(trace.comparisons() > 0);
+/// assert_eq!(trace.levels().first().unwrap().level, skiplist.stats().max_level - 1);
+///
+/// // A miss searches every level, from the top down to level 0
+/// let (value, trace) = skiplist.get_traced(&500);
+/// assert_eq!(value, None);
+/// let levels: Vec<_> = trace.levels().iter().map(|l| l.level).collect();
+/// assert_eq!(levels, (0..skiplist.stats().max_level).rev().collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchTrace {
+    /// Levels in the order they were visited (top down)
+    levels: Vec<LevelTrace>,
+}
+
+impl SearchTrace {
+    /// Per-level counts, from the top level the search started on down.
+    pub fn levels(&self) -> &[LevelTrace] {
+        &self.levels
+    }
+
+    /// Total nodes moved past on all levels.
+    pub fn steps(&self) -> usize {
+        self.levels.iter().map(|l| l.steps).sum()
+    }
+
+    /// Total key comparisons on all levels.
+    pub fn comparisons(&self) -> usize {
+        self.levels.iter().map(|l| l.comparisons).sum()
+    }
+
+    /// Total node locks taken on all levels.
+    pub fn locks(&self) -> usize {
+        self.levels.iter().map(|l| l.locks).sum()
+    }
+
+    /// Counts for the level currently being searched
+    fn current(&mut self, level: usize) -> &mut LevelTrace {
+        if self.levels.last().is_none_or(|l| l.level != level) {
+            self.levels.push(LevelTrace { level, ..LevelTrace::default() });
+        }
+        self.levels.last_mut().unwrap()
+    }
+}
+
+impl SearchObserver for SearchTrace {
+    fn on_descend(&mut self, level: usize) {
+        self.current(level);
+    }
+
+    fn on_step(&mut self, level: usize) {
+        self.current(level).steps += 1;
+    }
+
+    fn on_compare(&mut self, level: usize) {
+        self.current(level).comparisons += 1;
+    }
+
+    fn on_lock(&mut self, level: usize) {
+        self.current(level).locks += 1;
+    }
+}
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord,
+{
+    /// Retrieves a value by its key, reporting each step of the search to
+    /// `observer`.
+    ///
+    /// Returns the same value as [`get`](Self::get).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::{SearchObserver, SkipList};
+    ///
+    /// // Count only the comparisons
+    /// struct Comparisons(usize);
+    /// impl SearchObserver for Comparisons {
+    ///     fn on_compare(&mut self, _level: usize) {
+    ///         self.0 += 1;
+    ///     }
+    /// }
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.insert(1, "one");
+    ///
+    /// let mut counter = Comparisons(0);
+    /// assert_eq!(skiplist.get_observed(&1, &mut counter), Some("one"));
+    /// assert_eq!(counter.0, 1);
+    /// ```
+    pub fn get_observed<O>(&self, key: &K, observer: &mut O) -> Option<V>
+    where
+        V: Clone,
+        O: SearchObserver,
+    {
+        let Some(node) = self.find_node_observed(key, observer) else {
+            record!(self, misses);
+            return None;
+        };
+        record!(self, hits);
+        let node_ref = node.read().unwrap();
+        self.bounds.touch(&node_ref);
+        node_ref.val.clone()
+    }
+
+    /// Retrieves a value by its key along with a trace of the search.
+    ///
+    /// The trace lists, for every level visited, how many nodes the search
+    /// stepped past, how many keys it compared and how many locks it took.
+    pub fn get_traced(&self, key: &K) -> (Option<V>, SearchTrace)
+    where
+        V: Clone,
+    {
+        let mut trace = SearchTrace::default();
+        let value = self.get_observed(key, &mut trace);
+        (value, trace)
+    }
+}
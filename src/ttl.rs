@@ -0,0 +1,354 @@
+//! Skip list whose entries expire.
+//!
+//! A [`TtlSkipList`] gives every entry an optional deadline. Expired entries
+//! are invisible straight away, but they are only removed when something
+//! touches them:
+//!
+//! - **Lazily:** [`get`](TtlSkipList::get), [`remove`](TtlSkipList::remove)
+//!   and [`insert`](TtlSkipList::insert) drop an expired entry they land on.
+//! - **Actively:** [`purge_expired`](TtlSkipList::purge_expired) sweeps every
+//!   entry whose deadline has passed.
+//!
+//! The sweep reads a second [`SkipList`] keyed by `(deadline, key)`, so it
+//! visits only the expired entries, in deadline order, at O(log n) each
+//! instead of scanning the whole list.
+//!
+//! Time comes from a [`Clock`]. [`SystemClock`] is the real one; tests use
+//! [`ManualClock`] to move time forward by hand.
+
+use crate::SkipList;
+use std::iter::FusedIterator;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A source of the current time for a [`TtlSkipList`].
+pub trait Clock {
+    /// Returns the current instant.
+    fn now(&self) -> Instant;
+}
+
+/// The system's monotonic clock, [`Instant::now`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves when told to.
+///
+/// Clones share the same time, so a test can keep one handle and give another
+/// to the list.
+///
+/// # Examples
+///
+/// ```
+/// use skiplist_rs::{Clock, ManualClock};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::new();
+/// let start = clock.now();
+/// clock.clone().advance(Duration::from_secs(5));
+/// assert_eq!(clock.now() - start, Duration::from_secs(5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    start: Instant,
+    /// Nanoseconds elapsed since `start`
+    elapsed: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    /// Creates a clock stopped at the current instant.
+    pub fn new() -> Self {
+        ManualClock { start: Instant::now(), elapsed: Arc::new(AtomicU64::new(0)) }
+    }
+
+    /// Moves the clock, and every clone of it, forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        let nanos = u64::try_from(by.as_nanos()).unwrap_or(u64::MAX);
+        self.elapsed.fetch_add(nanos, Ordering::SeqCst);
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.start + Duration::from_nanos(self.elapsed.load(Ordering::SeqCst))
+    }
+}
+
+/// A stored value with its deadline (`None` never expires)
+#[derive(Clone)]
+struct Slot<V> {
+    value: V,
+    deadline: Option<Instant>,
+}
+
+impl<V> Slot<V> {
+    fn is_live(&self, now: Instant) -> bool {
+        self.deadline.is_none_or(|deadline| deadline > now)
+    }
+}
+
+/// A skip list whose entries can expire.
+///
+/// An entry inserted with a time-to-live expires once the clock reaches its
+/// deadline; from then on it behaves as if it had been removed.
+///
+/// # Examples
+///
+/// ```
+/// use skiplist_rs::{Clock, ManualClock, TtlSkipList};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::new();
+/// let mut cache = TtlSkipList::with_clock(clock.clone());
+/// cache.insert("session", 42, Duration::from_secs(30));
+/// cache.insert_permanent("config", 7);
+///
+/// clock.advance(Duration::from_secs(31));
+/// assert_eq!(cache.get(&"session"), None);
+/// assert_eq!(cache.get(&"config"), Some(7));
+/// ```
+pub struct TtlSkipList<K, V, C = SystemClock> {
+    entries: SkipList<K, Slot<V>>,
+    /// `(deadline, key)` for every entry that has a deadline
+    deadlines: SkipList<(Instant, K), ()>,
+    clock: C,
+}
+
+impl<K, V> TtlSkipList<K, V, SystemClock>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    /// Creates an empty list on the system clock.
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+}
+
+impl<K, V> Default for TtlSkipList<K, V, SystemClock>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, C> TtlSkipList<K, V, C>
+where
+    K: Ord + Clone,
+    V: Clone,
+    C: Clock,
+{
+    /// Creates an empty list that reads the time from `clock`.
+    pub fn with_clock(clock: C) -> Self {
+        TtlSkipList { entries: SkipList::new(), deadlines: SkipList::new(), clock }
+    }
+
+    /// Returns the list's clock.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Returns the number of stored entries, including expired ones that have
+    /// not been removed yet.
+    ///
+    /// Call [`purge_expired`](Self::purge_expired) first for an exact count of
+    /// live entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no entries are stored, expired or not.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Inserts an entry that expires `ttl` from now, returning the previous
+    /// live value for the key.
+    ///
+    /// Replacing an entry replaces its deadline too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::{ManualClock, TtlSkipList};
+    /// use std::time::Duration;
+    ///
+    /// let clock = ManualClock::new();
+    /// let mut cache = TtlSkipList::with_clock(clock.clone());
+    /// assert_eq!(cache.insert(1, "a", Duration::from_secs(10)), None);
+    ///
+    /// clock.advance(Duration::from_secs(10));
+    /// // The old value had already expired
+    /// assert_eq!(cache.insert(1, "b", Duration::from_secs(10)), None);
+    /// ```
+    pub fn insert(&mut self, key: K, value: V, ttl: Duration) -> Option<V> {
+        let now = self.clock.now();
+        // A deadline past the end of `Instant` is as good as never
+        self.insert_slot(key, Slot { value, deadline: now.checked_add(ttl) }, now)
+    }
+
+    /// Inserts an entry that never expires, returning the previous live value
+    /// for the key.
+    pub fn insert_permanent(&mut self, key: K, value: V) -> Option<V> {
+        let now = self.clock.now();
+        self.insert_slot(key, Slot { value, deadline: None }, now)
+    }
+
+    fn insert_slot(&mut self, key: K, slot: Slot<V>, now: Instant) -> Option<V> {
+        let deadline = slot.deadline;
+        let old = self.entries.insert(key.clone(), slot);
+        // The old deadline goes first: it may equal the new one
+        if let Some(old_deadline) = old.as_ref().and_then(|old| old.deadline) {
+            self.deadlines.remove(&(old_deadline, key.clone()));
+        }
+        if let Some(deadline) = deadline {
+            self.deadlines.insert((deadline, key), ());
+        }
+        old.filter(|old| old.is_live(now)).map(|old| old.value)
+    }
+
+    /// Returns the value for `key` if it has not expired.
+    ///
+    /// An expired entry found here is removed.
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let slot = self.entries.get(key)?;
+        if slot.is_live(self.clock.now()) {
+            return Some(slot.value);
+        }
+        self.remove_slot(key);
+        None
+    }
+
+    /// Returns `true` if `key` has a live entry, removing it if it has
+    /// expired.
+    pub fn contains_key(&mut self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns how long the entry for `key` has left, or `None` if there is
+    /// no live entry.
+    ///
+    /// A permanent entry has `Some(Duration::MAX)` left. Unlike
+    /// [`get`](Self::get), this never removes anything.
+    pub fn time_to_live(&self, key: &K) -> Option<Duration> {
+        let slot = self.entries.get(key)?;
+        let now = self.clock.now();
+        match slot.deadline {
+            None => Some(Duration::MAX),
+            Some(deadline) => (deadline > now).then(|| deadline - now),
+        }
+    }
+
+    /// Removes `key`, returning its value if it had not expired.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let slot = self.remove_slot(key)?;
+        slot.is_live(self.clock.now()).then_some(slot.value)
+    }
+
+    /// Remove `key` from both lists, expired or not
+    fn remove_slot(&mut self, key: &K) -> Option<Slot<V>> {
+        let slot = self.entries.remove(key)?;
+        if let Some(deadline) = slot.deadline {
+            self.deadlines.remove(&(deadline, key.clone()));
+        }
+        Some(slot)
+    }
+
+    /// Removes every entry whose deadline is at or before `now`, returning
+    /// how many were removed.
+    ///
+    /// Only the expired entries are visited, earliest deadline first, so a
+    /// sweep costs O(k log n) for k expired entries. Pass
+    /// `list.clock().now()` to purge everything expired so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::{Clock, ManualClock, TtlSkipList};
+    /// use std::time::Duration;
+    ///
+    /// let clock = ManualClock::new();
+    /// let mut cache = TtlSkipList::with_clock(clock.clone());
+    /// for i in 0..10u64 {
+    ///     cache.insert(i, i, Duration::from_secs(i + 1));
+    /// }
+    ///
+    /// clock.advance(Duration::from_secs(4));
+    /// assert_eq!(cache.purge_expired(cache.clock().now()), 4);
+    /// assert_eq!(cache.len(), 6);
+    /// ```
+    pub fn purge_expired(&mut self, now: Instant) -> usize {
+        let mut purged = 0;
+        while let Some(((deadline, key), ())) = self.deadlines.iter().next() {
+            if deadline > now {
+                break;
+            }
+            self.deadlines.remove(&(deadline, key.clone()));
+            self.entries.remove(&key);
+            purged += 1;
+        }
+        purged
+    }
+
+    /// Returns an iterator over the live entries in key order.
+    ///
+    /// Expiry is judged against the time when the iterator was created.
+    /// Expired entries are skipped but not removed.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { inner: self.entries.iter(), now: self.clock.now() }
+    }
+}
+
+/// Iterator over the live entries of a [`TtlSkipList`] in key order.
+///
+/// Created by [`TtlSkipList::iter`]. Supports `.rev()`.
+pub struct Iter<'a, K, V> {
+    inner: crate::Iter<'a, K, Slot<V>>,
+    now: Instant,
+}
+
+impl<K, V> Iterator for Iter<'_, K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let now = self.now;
+        self.inner.by_ref().find(|(_, slot)| slot.is_live(now)).map(|(key, slot)| (key, slot.value))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let now = self.now;
+        self.inner.by_ref().rfind(|(_, slot)| slot.is_live(now)).map(|(key, slot)| (key, slot.value))
+    }
+}
+
+impl<K, V> FusedIterator for Iter<'_, K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+}
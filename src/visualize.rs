@@ -0,0 +1,282 @@
+//! ASCII and Graphviz rendering of a skip list's towers.
+//!
+//! [`SkipList::to_ascii`] draws the same per-level lanes as the README:
+//!
+//! ```text
+//! Level 2: [1] ------------------------------> [15]
+//! Level 1: [1] -> [4] --------> [9] ---------> [15]
+//! Level 0: [1] -> [4] -> [6] -> [9] -> [12] -> [15]
+//! ```
+//!
+//! and [`SkipList::to_dot`] emits a Graphviz graph with one record per tower
+//! and one edge per forward pointer. Both can be limited to the first few
+//! entries with [`RenderOptions`] so large lists stay readable.
+
+use crate::{NodeRef, SkipList};
+use std::collections::HashMap;
+use std::fmt::{Display, Write};
+use std::sync::Arc;
+
+/// Options for [`SkipList::to_ascii_with`] and [`SkipList::to_dot_with`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Render at most this many entries (all of them when `None`)
+    pub max_nodes: Option<usize>,
+}
+
+impl RenderOptions {
+    /// Options that render at most `max_nodes` entries.
+    pub fn with_max_nodes(max_nodes: usize) -> Self {
+        Self { max_nodes: Some(max_nodes) }
+    }
+}
+
+/// The part of a list that fits in the rendering window
+struct Window<K, V> {
+    /// Nodes in level-0 order with their labels
+    nodes: Vec<(NodeRef<K, V>, String)>,
+    /// Entries left out of the window
+    hidden: usize,
+}
+
+impl<K, V> Window<K, V> {
+    fn height(&self, index: usize) -> usize {
+        self.nodes[index].0.read().unwrap().height()
+    }
+
+    /// Whether the node at `index` links past the window at `level`
+    fn links_past(&self, index: usize, level: usize) -> bool {
+        self.hidden > 0 && self.nodes[index].0.read().unwrap().fwd[level].is_some()
+    }
+
+    /// Tallest tower in the window
+    fn levels(&self) -> usize {
+        (0..self.nodes.len()).map(|i| self.height(i)).max().unwrap_or(0)
+    }
+}
+
+/// Escape text for use inside a Graphviz record label
+fn escape_record(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '{' | '}' | '|' | '<' | '>' | '"' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord + Display,
+{
+    /// Collect the nodes to render
+    fn window(&self, options: &RenderOptions) -> Window<K, V> {
+        let limit = options.max_nodes.unwrap_or(usize::MAX);
+        let mut nodes = Vec::new();
+        let mut next = self.head.read().unwrap().fwd[0].clone();
+
+        while let Some(node) = next {
+            if nodes.len() == limit {
+                break;
+            }
+            let label = match &node.read().unwrap().key {
+                Some(key) => key.to_string(),
+                None => String::new(),
+            };
+            next = node.read().unwrap().fwd[0].clone();
+            nodes.push((node, label));
+        }
+
+        let hidden = self.len.saturating_sub(nodes.len());
+        Window { nodes, hidden }
+    }
+
+    /// Renders every level as an ASCII lane, tallest level first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.insert(1, "one");
+    /// skiplist.insert(2, "two");
+    ///
+    /// let drawing = skiplist.to_ascii();
+    /// assert!(drawing.ends_with("Level 0: [1] -> [2]\n"));
+    /// ```
+    pub fn to_ascii(&self) -> String {
+        self.to_ascii_with(&RenderOptions::default())
+    }
+
+    /// Renders the list as ASCII lanes with the given options.
+    ///
+    /// When entries are left out, lanes that continue past the last rendered
+    /// entry end in `...` and a final line says how many were skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::{RenderOptions, SkipList};
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// for i in 0..100 {
+    ///     skiplist.insert(i, i);
+    /// }
+    ///
+    /// let drawing = skiplist.to_ascii_with(&RenderOptions::with_max_nodes(3));
+    /// assert!(drawing.contains("Level 0: [0] -> [1] -> [2] -> ..."));
+    /// assert!(drawing.ends_with("(97 more entries)\n"));
+    /// ```
+    pub fn to_ascii_with(&self, options: &RenderOptions) -> String {
+        let window = self.window(options);
+        if window.nodes.is_empty() {
+            return if window.hidden > 0 {
+                format!("({} more entries)\n", window.hidden)
+            } else {
+                "(empty)\n".to_string()
+            };
+        }
+
+        // Column where each cell starts, as laid out on level 0
+        let mut columns = Vec::with_capacity(window.nodes.len());
+        let mut width = 0;
+        for (_, label) in &window.nodes {
+            if width > 0 {
+                width += 4; // " -> "
+            }
+            columns.push(width);
+            width += label.len() + 2;
+        }
+        let ellipsis = width + 4;
+
+        let levels = window.levels();
+        let label_width = format!("Level {}:", levels - 1).len();
+        let mut out = String::new();
+
+        for level in (0..levels).rev() {
+            let mut line = format!("{:<width$} ", format!("Level {}:", level), width = label_width);
+            let start = line.len();
+            // End of the last cell drawn on this lane
+            let mut end: Option<usize> = None;
+
+            for (i, (_, label)) in window.nodes.iter().enumerate() {
+                if window.height(i) <= level {
+                    continue;
+                }
+                match end {
+                    Some(end) => {
+                        let dashes = columns[i] - end - 3;
+                        line.push(' ');
+                        line.push_str(&"-".repeat(dashes));
+                        line.push_str("> ");
+                    }
+                    None => line.push_str(&" ".repeat(columns[i])),
+                }
+                let _ = write!(line, "[{}]", label);
+                end = Some(line.len() - start);
+            }
+
+            let last = (0..window.nodes.len()).rev().find(|&i| window.height(i) > level);
+            if let (Some(end), Some(last)) = (end, last) {
+                if window.links_past(last, level) {
+                    line.push(' ');
+                    line.push_str(&"-".repeat(ellipsis - end - 3));
+                    line.push_str("> ...");
+                }
+            }
+
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+
+        if window.hidden > 0 {
+            let _ = writeln!(out, "({} more entries)", window.hidden);
+        }
+        out
+    }
+
+    /// Renders the list as a Graphviz DOT graph.
+    ///
+    /// Each tower is a record with one field per level, and every forward
+    /// pointer (including the head's) becomes an edge between fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist_rs::SkipList;
+    ///
+    /// let mut skiplist = SkipList::new();
+    /// skiplist.insert(1, "one");
+    ///
+    /// let dot = skiplist.to_dot();
+    /// assert!(dot.starts_with("digraph skiplist {"));
+    /// assert!(dot.contains("head:l0 -> n0:l0;"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with(&RenderOptions::default())
+    }
+
+    /// Renders the list as a Graphviz DOT graph with the given options.
+    ///
+    /// When entries are left out, links leaving the rendered part point at a
+    /// single `more` node that says how many were skipped.
+    pub fn to_dot_with(&self, options: &RenderOptions) -> String {
+        let window = self.window(options);
+        let index: HashMap<*const _, usize> = window
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, (node, _))| (Arc::as_ptr(node), i))
+            .collect();
+
+        let mut out = String::new();
+        out.push_str("digraph skiplist {\n");
+        out.push_str("    rankdir=LR;\n");
+        out.push_str("    node [shape=record];\n");
+
+        let fields = |height: usize, text: &str| {
+            (0..height)
+                .rev()
+                .map(|level| format!("<l{}> {}", level, text))
+                .collect::<Vec<_>>()
+                .join("|")
+        };
+
+        let head = self.head.read().unwrap();
+        let _ = writeln!(out, "    head [label=\"{}\"];", fields(head.height(), "head"));
+        for (i, (node, label)) in window.nodes.iter().enumerate() {
+            let height = node.read().unwrap().height();
+            let _ = writeln!(out, "    n{} [label=\"{}\"];", i, fields(height, &escape_record(label)));
+        }
+        if window.hidden > 0 {
+            let _ = writeln!(out, "    more [shape=plaintext, label=\"... {} more\"];", window.hidden);
+        }
+
+        let target = |link: &Option<NodeRef<K, V>>, level: usize| {
+            let node = link.as_ref()?;
+            Some(match index.get(&Arc::as_ptr(node)) {
+                Some(i) => format!("n{}:l{}", i, level),
+                None => "more".to_string(),
+            })
+        };
+
+        for (level, link) in head.fwd.iter().enumerate() {
+            if let Some(to) = target(link, level) {
+                let _ = writeln!(out, "    head:l{} -> {};", level, to);
+            }
+        }
+        for (i, (node, _)) in window.nodes.iter().enumerate() {
+            for (level, link) in node.read().unwrap().fwd.iter().enumerate() {
+                if let Some(to) = target(link, level) {
+                    let _ = writeln!(out, "    n{}:l{} -> {};", i, level, to);
+                }
+            }
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
@@ -0,0 +1,144 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use skiplist_rs::{AugmentedSkipList, Count, Max, Min, Monoid, Sum};
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+/// Concatenation: associative but not commutative, so it checks key order
+struct Concat;
+
+impl Monoid<char> for Concat {
+    type Agg = String;
+
+    fn empty() -> String {
+        String::new()
+    }
+
+    fn lift(value: &char) -> String {
+        value.to_string()
+    }
+
+    fn combine(left: &String, right: &String) -> String {
+        format!("{}{}", left, right)
+    }
+}
+
+thread_local! {
+    static COMBINES: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Sum that counts how often it combines
+struct CountingSum;
+
+impl Monoid<u64> for CountingSum {
+    type Agg = u64;
+
+    fn empty() -> u64 {
+        0
+    }
+
+    fn lift(value: &u64) -> u64 {
+        *value
+    }
+
+    fn combine(left: &u64, right: &u64) -> u64 {
+        COMBINES.with(|c| c.set(c.get() + 1));
+        left + right
+    }
+}
+
+/// Every built-in aggregate at once
+type All = (Sum, (Count, (Min, Max)));
+
+#[test]
+fn test_sum_matches_a_scan_through_random_edits() {
+    let mut rng = StdRng::seed_from_u64(45);
+    let mut list: AugmentedSkipList<u32, i64, All> = AugmentedSkipList::new();
+    list.seed(1);
+    let mut model = BTreeMap::new();
+
+    for step in 0..3000 {
+        let key = rng.gen_range(0..500);
+        if rng.gen_ratio(2, 3) {
+            let value = rng.gen_range(-1000..1000);
+            assert_eq!(list.insert(key, value), model.insert(key, value));
+        } else {
+            assert_eq!(list.remove(&key), model.remove(&key));
+        }
+        assert_eq!(list.len(), model.len());
+
+        if step % 10 == 0 {
+            let a = rng.gen_range(0..520);
+            let b = rng.gen_range(0..520);
+            let bounds = [
+                (Bound::Included(a), Bound::Included(b)),
+                (Bound::Included(a), Bound::Excluded(b)),
+                (Bound::Excluded(a), Bound::Unbounded),
+                (Bound::Unbounded, Bound::Excluded(b)),
+                (Bound::Unbounded, Bound::Unbounded),
+            ];
+            for range in bounds {
+                let values: Vec<i64> = if a > b && !matches!(range, (_, Bound::Unbounded) | (Bound::Unbounded, _)) {
+                    Vec::new()
+                } else {
+                    model.range(range).map(|(_, v)| *v).collect()
+                };
+                let expected = (
+                    values.iter().sum::<i64>(),
+                    (values.len(), (values.iter().min().copied(), values.iter().max().copied())),
+                );
+                assert_eq!(list.aggregate(range), expected, "{:?}", range);
+            }
+        }
+    }
+    assert!(list.iter().map(|(k, v)| (*k, *v)).eq(model.into_iter()));
+}
+
+#[test]
+fn test_aggregates_combine_in_key_order() {
+    let mut list: AugmentedSkipList<u32, char, Concat> = AugmentedSkipList::new();
+    for (i, c) in "skiplist".char_indices().rev() {
+        list.insert(i as u32, c);
+    }
+    assert_eq!(list.aggregate(..), "skiplist");
+    assert_eq!(list.aggregate(2..6), "ipli");
+
+    list.insert(4, 'L');
+    list.remove(&0);
+    assert_eq!(list.aggregate(..=4), "kipL");
+    assert_eq!(list.aggregate(10..), "");
+}
+
+#[test]
+fn test_aggregate_is_logarithmic() {
+    let mut list: AugmentedSkipList<u64, u64, CountingSum> = AugmentedSkipList::new();
+    list.seed(3);
+    let n = 100_000;
+    for k in 0..n {
+        list.insert(k, k);
+    }
+
+    COMBINES.with(|c| c.set(0));
+    assert_eq!(list.aggregate(1000..=90_000), (1000..=90_000).sum::<u64>());
+    let combines = COMBINES.with(Cell::get);
+    // A scan would combine ~89,000 times
+    assert!(combines < 200, "{} combines", combines);
+}
+
+#[test]
+fn test_get_and_empty_list() {
+    let mut list: AugmentedSkipList<&str, u32, Sum> = AugmentedSkipList::new();
+    assert_eq!(list.aggregate(..), 0);
+    assert!(list.is_empty());
+
+    list.insert("b", 2);
+    list.insert("a", 1);
+    assert_eq!(list.get(&"a"), Some(&1));
+    assert_eq!(list.get(&"c"), None);
+    assert_eq!(list.insert("a", 10), Some(1));
+    assert_eq!(list.aggregate(.."b"), 10);
+    assert_eq!(list.remove(&"a"), Some(10));
+    assert_eq!(list.remove(&"a"), None);
+    assert_eq!(list.aggregate(..), 2);
+}
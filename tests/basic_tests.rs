@@ -134,4 +134,46 @@ fn test_skiplist_ordering() {
     // Verify boundary values still work
     assert_eq!(skiplist.get(&5), Some("value_5".to_string()));   // Minimum
     assert_eq!(skiplist.get(&95), Some("value_95".to_string())); // Maximum
-} 
\ No newline at end of file
+}
+
+#[test]
+fn test_remove() {
+    let mut skiplist = SkipList::new();
+
+    for i in 0..100 {
+        skiplist.insert(i, format!("value_{}", i));
+    }
+
+    // Remove every even key
+    for i in (0..100).step_by(2) {
+        assert_eq!(skiplist.remove(&i), Some(format!("value_{}", i)));
+    }
+    assert_eq!(skiplist.len(), 50);
+
+    // Removed keys are gone, the rest are untouched
+    for i in 0..100 {
+        if i % 2 == 0 {
+            assert_eq!(skiplist.get(&i), None);
+        } else {
+            assert_eq!(skiplist.get(&i), Some(format!("value_{}", i)));
+        }
+    }
+
+    // Removing a missing key changes nothing
+    assert_eq!(skiplist.remove(&0), None);
+    assert_eq!(skiplist.remove(&1000), None);
+    assert_eq!(skiplist.len(), 50);
+}
+
+#[test]
+fn test_remove_then_reinsert() {
+    let mut skiplist = SkipList::new();
+
+    skiplist.insert(1, "one");
+    assert_eq!(skiplist.remove(&1), Some("one"));
+    assert!(skiplist.is_empty());
+
+    assert_eq!(skiplist.insert(1, "uno"), None);
+    assert_eq!(skiplist.get(&1), Some("uno"));
+    assert_eq!(skiplist.len(), 1);
+}
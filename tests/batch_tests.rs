@@ -0,0 +1,64 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use skiplist_rs::{Capacity, EvictionPolicy, SkipList};
+use std::collections::BTreeMap;
+
+#[test]
+fn test_batches_match_one_at_a_time() {
+    let mut rng = StdRng::seed_from_u64(49);
+    let mut batched = SkipList::new();
+    let mut model = BTreeMap::new();
+
+    for round in 0..50 {
+        let entries: Vec<(u32, u32)> = (0..rng.gen_range(0..200)).map(|i| (rng.gen_range(0..1000), round * 1000 + i)).collect();
+        let expected: Vec<_> = entries.iter().map(|&(k, v)| model.insert(k, v)).collect();
+        assert_eq!(batched.insert_batch(entries), expected);
+
+        let keys: Vec<u32> = (0..rng.gen_range(0..100)).map(|_| rng.gen_range(0..1100)).collect();
+        let expected: Vec<_> = keys.iter().map(|k| model.get(k).copied()).collect();
+        assert_eq!(batched.get_many(&keys), expected);
+
+        let keys: Vec<u32> = (0..rng.gen_range(0..100)).map(|_| rng.gen_range(0..1100)).collect();
+        let expected: Vec<_> = keys.iter().map(|k| model.remove(k)).collect();
+        assert_eq!(batched.remove_batch(&keys), expected);
+
+        assert_eq!(batched.check_invariants(), Ok(()));
+        assert_eq!(batched.iter().collect::<Vec<_>>(), model.clone().into_iter().collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn test_batch_grows_the_list_from_empty() {
+    let mut skiplist = SkipList::new();
+    let results = skiplist.insert_batch((0..10_000).rev().map(|i| (i, i * 2)));
+    assert!(results.iter().all(Option::is_none));
+    assert_eq!(skiplist.len(), 10_000);
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+
+    let keys: Vec<i32> = (0..10_000).step_by(7).collect();
+    let values = skiplist.get_many(&keys);
+    assert!(keys.iter().zip(&values).all(|(k, v)| *v == Some(k * 2)));
+
+    let removed = skiplist.remove_batch(&keys);
+    assert_eq!(removed, values);
+    assert_eq!(skiplist.len(), 10_000 - keys.len());
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+    assert_eq!(skiplist.iter().next_back(), Some((9_999, 19_998)));
+}
+
+#[test]
+fn test_empty_batches() {
+    let mut skiplist: SkipList<i32, i32> = SkipList::new();
+    assert!(skiplist.insert_batch(Vec::new()).is_empty());
+    assert!(skiplist.get_many(&[]).is_empty());
+    assert!(skiplist.remove_batch(&[]).is_empty());
+    assert_eq!(skiplist.get_many(&[1]), vec![None]);
+}
+
+#[test]
+fn test_insert_batch_enforces_capacity_at_the_end() {
+    let mut skiplist = SkipList::new();
+    skiplist.set_capacity(Capacity::Entries(3), EvictionPolicy::SmallestKey);
+    skiplist.insert_batch((0..10).map(|i| (i, i)));
+    assert_eq!(skiplist.iter().map(|(k, _)| k).collect::<Vec<_>>(), vec![7, 8, 9]);
+}
@@ -0,0 +1,272 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use skiplist_rs::{Capacity, EvictionPolicy, Inserted, SkipList};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn bounded(capacity: Capacity, policy: EvictionPolicy) -> SkipList<u32, String> {
+    let mut skiplist = SkipList::new();
+    skiplist.set_capacity(capacity, policy);
+    skiplist
+}
+
+#[test]
+fn test_smallest_key_policy_keeps_the_largest_keys() {
+    let mut skiplist = bounded(Capacity::Entries(3), EvictionPolicy::SmallestKey);
+    for i in [5, 1, 9, 3, 7] {
+        skiplist.insert(i, i.to_string());
+    }
+    assert_eq!(skiplist.iter().map(|(k, _)| k).collect::<Vec<_>>(), vec![5, 7, 9]);
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+
+    // A key below everything kept is evicted straight away
+    let outcome = skiplist.insert_evicting(0, "zero".to_string());
+    assert_eq!(outcome, Inserted { replaced: None, evicted: vec![(0, "zero".to_string())] });
+}
+
+#[test]
+fn test_largest_key_policy_keeps_the_smallest_keys() {
+    let mut skiplist = bounded(Capacity::Entries(2), EvictionPolicy::LargestKey);
+    for i in 0..10 {
+        skiplist.insert(i, i.to_string());
+    }
+    assert_eq!(skiplist.iter().map(|(k, _)| k).collect::<Vec<_>>(), vec![0, 1]);
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_least_recently_used_policy() {
+    let mut skiplist = bounded(Capacity::Entries(3), EvictionPolicy::LeastRecentlyUsed);
+    for i in 1..=3 {
+        skiplist.insert(i, i.to_string());
+    }
+    // Reads and updates both count as use
+    skiplist.get(&1);
+    skiplist.insert(2, "two".to_string());
+
+    assert_eq!(skiplist.insert_evicting(4, "4".to_string()).evicted, vec![(3, "3".to_string())]);
+    assert_eq!(skiplist.insert_evicting(5, "5".to_string()).evicted, vec![(1, "1".to_string())]);
+    assert_eq!(skiplist.iter().map(|(k, _)| k).collect::<Vec<_>>(), vec![2, 4, 5]);
+}
+
+#[test]
+fn test_least_recently_used_matches_a_recency_model() {
+    let mut rng = StdRng::seed_from_u64(44);
+    let mut skiplist = bounded(Capacity::Entries(16), EvictionPolicy::LeastRecentlyUsed);
+    // Keys from least to most recently used
+    let mut recency: Vec<u32> = Vec::new();
+
+    for _ in 0..5000 {
+        let key = rng.gen_range(0..40);
+        let used = |recency: &mut Vec<u32>| {
+            recency.retain(|k| *k != key);
+            recency.push(key);
+        };
+        match rng.gen_range(0..3) {
+            0 => {
+                let evicted = skiplist.insert_evicting(key, key.to_string()).evicted;
+                used(&mut recency);
+                let expected: Vec<_> = if recency.len() > 16 { vec![recency.remove(0)] } else { vec![] };
+                assert_eq!(evicted.into_iter().map(|(k, _)| k).collect::<Vec<_>>(), expected);
+            }
+            1 => {
+                if skiplist.get(&key).is_some() {
+                    used(&mut recency);
+                }
+            }
+            _ => {
+                skiplist.remove(&key);
+                recency.retain(|k| *k != key);
+            }
+        }
+    }
+    assert_eq!(skiplist.len(), recency.len());
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_least_recently_used_order_survives_split_off() {
+    let mut skiplist = SkipList::new();
+    for i in 0..6 {
+        skiplist.insert(i, i.to_string());
+    }
+    // Limiting an existing list starts every entry off in key order
+    skiplist.set_capacity(Capacity::Entries(6), EvictionPolicy::LeastRecentlyUsed);
+    skiplist.get(&0);
+
+    let right = skiplist.split_off(&4);
+    assert_eq!(right.len(), 2);
+    assert_eq!(skiplist.insert_evicting(10, "10".to_string()).evicted, vec![]);
+    assert_eq!(skiplist.insert_evicting(11, "11".to_string()).evicted, vec![]);
+    assert_eq!(skiplist.insert_evicting(12, "12".to_string()).evicted, vec![(1, "1".to_string())]);
+
+    let evicted = skiplist.set_capacity(Capacity::Entries(3), EvictionPolicy::LeastRecentlyUsed);
+    assert_eq!(evicted.into_iter().map(|(k, _)| k).collect::<Vec<_>>(), vec![2, 3, 0]);
+    assert_eq!(skiplist.iter().map(|(k, _)| k).collect::<Vec<_>>(), vec![10, 11, 12]);
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_least_recently_used_survives_concurrent_reads_of_one_entry() {
+    let mut skiplist = bounded(Capacity::Entries(4), EvictionPolicy::LeastRecentlyUsed);
+    for i in 0..4 {
+        skiplist.insert(i, i.to_string());
+    }
+
+    // Every reader restamps the same node at once
+    thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                for _ in 0..1000 {
+                    skiplist.get(&0);
+                }
+            });
+        }
+    });
+
+    for i in 4..104 {
+        skiplist.insert(i, i.to_string());
+        assert_eq!(skiplist.len(), 4);
+    }
+    assert_eq!(skiplist.iter().map(|(k, _)| k).collect::<Vec<_>>(), vec![100, 101, 102, 103]);
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_updates_do_not_evict_under_entry_capacity() {
+    let mut skiplist = bounded(Capacity::Entries(2), EvictionPolicy::SmallestKey);
+    skiplist.insert(1, "a".to_string());
+    skiplist.insert(2, "b".to_string());
+    let outcome = skiplist.insert_evicting(1, "A".to_string());
+    assert_eq!(outcome, Inserted { replaced: Some("a".to_string()), evicted: vec![] });
+}
+
+#[test]
+fn test_callback_sees_every_eviction() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+
+    let mut skiplist = SkipList::new();
+    skiplist.on_evict(move |k: &u32, v: &String| sink.lock().unwrap().push((*k, v.clone())));
+    for i in 0..10 {
+        skiplist.insert(i, i.to_string());
+    }
+    let evicted = skiplist.set_capacity(Capacity::Entries(7), EvictionPolicy::SmallestKey);
+    skiplist.insert(10, "10".to_string());
+    skiplist.insert_evicting(11, "11".to_string());
+
+    let keys: Vec<_> = seen.lock().unwrap().iter().map(|(k, _)| *k).collect();
+    assert_eq!(keys, vec![0, 1, 2, 3, 4]);
+    assert_eq!(evicted.len(), 3);
+}
+
+#[test]
+fn test_byte_capacity_counts_nodes_and_weighed_heap() {
+    let mut skiplist: SkipList<u32, String> = SkipList::new();
+    skiplist.seed(7);
+    skiplist.set_weigher(|_, v| v.capacity());
+    for i in 0..50 {
+        skiplist.insert(i, "x".repeat(100));
+    }
+    let full = skiplist.memory_usage();
+    assert!(full > 50 * 100);
+
+    // Half the bytes keeps roughly half the entries
+    skiplist.set_capacity(Capacity::Bytes(full / 2), EvictionPolicy::SmallestKey);
+    assert!(skiplist.memory_usage() <= full / 2);
+    assert!((20..=30).contains(&skiplist.len()), "{} entries left", skiplist.len());
+
+    // Growing a value can push the list over and evict another entry
+    let before = skiplist.len();
+    let first = skiplist.iter().next().unwrap().0;
+    let last = skiplist.iter().next_back().unwrap().0;
+    let outcome = skiplist.insert_evicting(last, "x".repeat(1000));
+    assert!(outcome.evicted.iter().any(|(k, _)| *k == first));
+    assert!(skiplist.len() < before);
+    assert!(skiplist.memory_usage() <= full / 2);
+
+    // The running count matches a fresh walk
+    let tracked = skiplist.memory_usage();
+    skiplist.clear_capacity();
+    assert_eq!(skiplist.memory_usage(), tracked);
+}
+
+#[test]
+fn test_removal_and_drain_release_bytes() {
+    let mut skiplist: SkipList<u32, String> = SkipList::new();
+    skiplist.set_capacity(Capacity::Bytes(usize::MAX), EvictionPolicy::SmallestKey);
+    for i in 0..20 {
+        skiplist.insert(i, i.to_string());
+    }
+    skiplist.remove(&3);
+    skiplist.retain(|k, _| k % 2 == 0);
+    let mut cursor = skiplist.cursor_front_mut();
+    cursor.set_value("a much longer value than before".to_string());
+    cursor.remove_current();
+
+    let tracked = skiplist.memory_usage();
+    skiplist.clear_capacity();
+    assert_eq!(skiplist.memory_usage(), tracked);
+
+    skiplist.set_capacity(Capacity::Bytes(usize::MAX), EvictionPolicy::SmallestKey);
+    skiplist.drain();
+    assert_eq!(skiplist.memory_usage(), 0);
+}
+
+#[test]
+fn test_retain_reweighs_values_it_changes() {
+    let mut skiplist: SkipList<u32, String> = SkipList::new();
+    skiplist.set_weigher(|_, v: &String| v.len());
+    skiplist.set_capacity(Capacity::Bytes(1_000_000), EvictionPolicy::SmallestKey);
+    for i in 0..4 {
+        skiplist.insert(i, i.to_string());
+    }
+
+    skiplist.retain(|_, v| {
+        v.push_str(&"x".repeat(1_000_000));
+        true
+    });
+    let tracked = skiplist.memory_usage();
+    assert!(tracked > 4_000_000);
+
+    // The tally matches a fresh walk, and the next insert evicts to fit
+    skiplist.clear_capacity();
+    assert_eq!(skiplist.memory_usage(), tracked);
+    skiplist.set_capacity(Capacity::Bytes(1_000_000), EvictionPolicy::SmallestKey);
+    skiplist.insert(10, "10".to_string());
+    assert_eq!(skiplist.iter().map(|(k, _)| k).collect::<Vec<_>>(), vec![10]);
+
+    // A value grown and then removed by the predicate releases its new size
+    skiplist.set_capacity(Capacity::Bytes(usize::MAX), EvictionPolicy::SmallestKey);
+    skiplist.remove(&10);
+    for i in 0..4 {
+        skiplist.insert(i, i.to_string());
+    }
+    let removed: Vec<_> = skiplist
+        .extract_if(|k, v| {
+            v.push_str("grown");
+            k % 2 == 0
+        })
+        .map(|(k, _)| k)
+        .collect();
+    assert_eq!(removed, vec![0, 2]);
+    let tracked = skiplist.memory_usage();
+    skiplist.clear_capacity();
+    assert_eq!(skiplist.memory_usage(), tracked);
+}
+
+#[test]
+fn test_append_enforces_capacity() {
+    let mut left = bounded(Capacity::Entries(4), EvictionPolicy::SmallestKey);
+    for i in 0..3 {
+        left.insert(i, i.to_string());
+    }
+    let mut right = SkipList::new();
+    for i in 10..13 {
+        right.insert(i, i.to_string());
+    }
+    left.append(&mut right);
+    assert_eq!(left.iter().map(|(k, _)| k).collect::<Vec<_>>(), vec![2, 10, 11, 12]);
+    assert!(right.is_empty());
+    assert_eq!(left.check_invariants(), Ok(()));
+}
@@ -0,0 +1,221 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Run the binary with `args`, feeding `input` on stdin, and return stdout
+fn run(args: &[&str], input: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_skiplist-rs"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("binary starts");
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_repl_basic_commands() {
+    let output = run(&[], "put 2 two\nput 1 one\nput 2 TWO\nget 2\nget 3\ndel 1\ndel 1\nlen\n");
+    let lines: Vec<_> = output.lines().collect();
+    assert_eq!(
+        lines,
+        vec!["inserted", "inserted", "updated (was two)", "TWO", "(not found)", "removed one", "(not found)", "1"]
+    );
+}
+
+#[test]
+fn test_repl_values_keep_spaces() {
+    let output = run(&[], "put 7 hello   world\nget 7\n");
+    assert!(output.ends_with("hello   world\n"));
+}
+
+#[test]
+fn test_repl_range() {
+    let script: String = (0..10).map(|i| format!("put {} v{}\n", i, i)).collect();
+    let output = run(&[], &(script + "range 3 5\nrange 8\n"));
+    assert!(output.contains("3 => v3\n4 => v4\n5 => v5\n(3 entries)\n"));
+    assert!(output.contains("8 => v8\n9 => v9\n(2 entries)\n"));
+}
+
+#[test]
+fn test_repl_errors_do_not_stop_the_script() {
+    let output = run(&[], "frobnicate\nput x 1\nput 1\nget\nlen\n");
+    let lines: Vec<_> = output.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[..4].iter().all(|line| line.starts_with("error: ")));
+    assert_eq!(lines[4], "0");
+}
+
+#[test]
+fn test_repl_seed_makes_show_reproducible() {
+    let script: String = (0..40).map(|i| format!("put {} v\n", i)).collect();
+    let script = format!("seed 99\n{}show 10\nquit\nput 100 never\n", script);
+    let first = run(&[], &script);
+    assert_eq!(first, run(&[], &script));
+    assert!(first.contains("Level 0: [0] -> [1]"));
+    assert!(first.ends_with("(30 more entries)\n"));
+}
+
+#[test]
+fn test_repl_save_and_load() {
+    let path = std::env::temp_dir().join(format!("skiplist-cli-{}.snap", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    let output = run(&[], &format!("put 1 one\nput 2 two\nsave {}\nshow\n", path));
+    let shape = output.lines().skip(3).collect::<Vec<_>>().join("\n");
+
+    let output = run(&[], &format!("load {}\nget 2\ncheck\nshow\n", path));
+    std::fs::remove_file(path).unwrap();
+    let lines: Vec<_> = output.lines().collect();
+    assert_eq!(lines[0], format!("loaded 2 entries from {}", path));
+    assert_eq!(lines[1], "two");
+    assert_eq!(lines[2], "ok");
+    assert_eq!(lines[3..].join("\n"), shape);
+}
+
+#[test]
+fn test_unknown_subcommand_fails() {
+    let status = Command::new(env!("CARGO_BIN_EXE_skiplist-rs"))
+        .arg("nonsense")
+        .stderr(Stdio::null())
+        .status()
+        .unwrap();
+    assert!(!status.success());
+}
+
+/// Run `bench` with `args`, returning (success, stdout, stderr)
+fn bench(args: &[&str]) -> (bool, String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_skiplist-rs"))
+        .arg("bench")
+        .args(args)
+        .output()
+        .unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn test_bench_text_report() {
+    let (ok, out, _) = bench(&["--threads", "2", "--ops", "2k", "--keys", "500", "--dist", "zipf:0.9", "--seed", "3"]);
+    assert!(ok);
+    assert!(out.contains("Threads:         2\n"));
+    assert!(out.contains("Operations:      2000 "));
+    assert!(out.contains("Keys:            500 (zipf:0.9)\n"));
+    assert!(out.contains("Throughput:"));
+    assert!(out.contains("Latency (ns):    p50 "));
+    assert!(out.contains("Elements:        500\n"));
+}
+
+#[test]
+fn test_bench_json_report() {
+    let (ok, out, _) = bench(&[
+        "--threads", "3", "--ops", "1000", "--keys", "100", "--mix", "read=50,write=25,delete=25",
+        "--dist", "hotspot:0.1,0.9", "--target", "mutex", "--format", "json", "--seed", "11", "--empty",
+    ]);
+    assert!(ok);
+    assert!(out.starts_with("{\"target\":\"mutex\",\"threads\":3,\"ops\":1000,"));
+    assert!(out.contains("\"dist\":\"hotspot:0.1,0.9\""));
+    assert!(out.contains("\"latency_ns\":{\"p50\":"));
+    assert!(out.trim_end().ends_with("}}"));
+
+    // The three operation counts add up to the requested total
+    let counts = out.split("\"counts\":{").nth(1).unwrap().split('}').next().unwrap();
+    let total: u64 = counts.split(',').map(|kv| kv.split(':').nth(1).unwrap().parse::<u64>().unwrap()).sum();
+    assert_eq!(total, 1000);
+}
+
+#[test]
+fn test_bench_sequential_writes_fill_the_key_space() {
+    let (ok, out, _) = bench(&[
+        "--ops", "300", "--keys", "300", "--mix", "write=100", "--dist", "sequential", "--empty", "--format", "json",
+    ]);
+    assert!(ok);
+    assert!(out.contains("\"stats\":{\"len\":300,"));
+}
+
+#[test]
+fn test_bench_rejects_bad_options() {
+    for args in [
+        &["--mix", "read=80,write=30"][..],
+        &["--mix", "read=4294967295,write=1,delete=100"],
+        &["--dist", "zipf:1.5"],
+        &["--dist", "pareto"],
+        &["--threads", "0"],
+        &["--ops"],
+        &["--frobnicate", "1"],
+    ] {
+        let (ok, _, err) = bench(args);
+        assert!(!ok, "{:?} was accepted", args);
+        assert!(err.starts_with("error: "));
+    }
+}
+
+/// Run the binary with `args` and `input` on stdin, returning (success, stdout, stderr)
+fn run_status(args: &[&str], input: &str) -> (bool, String, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_skiplist-rs"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn test_import_then_export_and_load_in_repl() {
+    let path = std::env::temp_dir().join(format!("skiplist-cli-import-{}.skpl", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    let csv = "key,value\n3,\"three, \"\"3\"\"\"\n1,one\n2,two\n1,uno\n";
+    let (ok, _, err) = run_status(&["import", "-", path, "--format", "csv"], csv);
+    assert!(ok, "{}", err);
+    assert!(err.contains("imported 4 records"));
+    assert!(err.contains("3 inserted, 1 overwritten, 0 skipped"));
+
+    let (ok, out, _) = run_status(&["export", path, "-", "--format", "jsonl"], "");
+    assert!(ok);
+    assert_eq!(out, "{\"key\":1,\"value\":\"uno\"}\n{\"key\":2,\"value\":\"two\"}\n{\"key\":3,\"value\":\"three, \\\"3\\\"\"}\n");
+
+    // Integer-keyed snapshots are the shell's own format
+    let output = run(&[], &format!("load {}\nget 3\n", path));
+    std::fs::remove_file(path).unwrap();
+    assert!(output.ends_with("three, \"3\"\n"));
+}
+
+#[test]
+fn test_import_duplicate_error_writes_nothing() {
+    let path = std::env::temp_dir().join(format!("skiplist-cli-dup-{}.skpl", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    let (ok, _, err) = run_status(&["import", "-", path, "--format", "csv", "--no-header", "--duplicates", "error"], "1,a\n2,b\n1,c\n");
+    assert!(!ok);
+    assert_eq!(err, "error: -: line 3: duplicate key `1`\n");
+    assert!(!std::path::Path::new(path).exists());
+}
+
+#[test]
+fn test_import_export_reject_bad_options() {
+    for args in [
+        &["import", "-", "out.skpl"][..],
+        &["import", "in.csv"],
+        &["import", "-", "out.skpl", "--format", "xml"],
+        &["import", "-", "out.skpl", "--format", "csv", "--key", "float"],
+        &["export", "in.skpl", "-", "--format", "csv", "--duplicates", "error"],
+    ] {
+        let (ok, _, err) = run_status(args, "");
+        assert!(!ok, "{:?} was accepted", args);
+        assert!(err.starts_with("error: "));
+    }
+}
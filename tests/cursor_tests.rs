@@ -0,0 +1,174 @@
+use skiplist_rs::SkipList;
+
+fn sample() -> SkipList<i32, String> {
+    let mut skiplist = SkipList::new();
+    for i in (10..=50).step_by(10) {
+        skiplist.insert(i, format!("value_{}", i));
+    }
+    skiplist
+}
+
+#[test]
+fn test_cursor_forward_and_back() {
+    let skiplist = sample();
+    let mut cursor = skiplist.cursor_front();
+
+    // Walk forward over every entry and into the ghost
+    let mut keys = vec![];
+    while let Some(key) = cursor.key() {
+        keys.push(key);
+        cursor.move_next();
+    }
+    assert_eq!(keys, vec![10, 20, 30, 40, 50]);
+    assert!(cursor.is_ghost());
+
+    // From the ghost, moving back lands on the last entry
+    let mut keys = vec![];
+    cursor.move_prev();
+    while let Some(key) = cursor.key() {
+        keys.push(key);
+        cursor.move_prev();
+    }
+    assert_eq!(keys, vec![50, 40, 30, 20, 10]);
+    assert!(cursor.is_ghost());
+}
+
+#[test]
+fn test_lower_bound_cursor() {
+    let skiplist = sample();
+
+    assert_eq!(skiplist.lower_bound_cursor(&30).key(), Some(30)); // Exact match
+    assert_eq!(skiplist.lower_bound_cursor(&25).key(), Some(30)); // Between keys
+    assert_eq!(skiplist.lower_bound_cursor(&0).key(), Some(10)); // Before all
+    assert!(skiplist.lower_bound_cursor(&51).is_ghost()); // After all
+}
+
+#[test]
+fn test_cursor_peek() {
+    let skiplist = sample();
+    let cursor = skiplist.lower_bound_cursor(&30);
+
+    assert_eq!(cursor.current(), Some((30, "value_30".to_string())));
+    assert_eq!(cursor.peek_next(), Some((40, "value_40".to_string())));
+    assert_eq!(cursor.peek_prev(), Some((20, "value_20".to_string())));
+
+    // Peeking past either end sees the ghost
+    let front = skiplist.cursor_front();
+    assert_eq!(front.peek_prev(), None);
+    let back = skiplist.lower_bound_cursor(&50);
+    assert_eq!(back.peek_next(), None);
+}
+
+#[test]
+fn test_cursor_on_empty_list() {
+    let mut skiplist: SkipList<i32, String> = SkipList::new();
+
+    let mut cursor = skiplist.cursor_front();
+    assert!(cursor.is_ghost());
+    cursor.move_next();
+    assert!(cursor.is_ghost());
+    cursor.move_prev();
+    assert!(cursor.is_ghost());
+
+    let mut cursor = skiplist.cursor_front_mut();
+    assert_eq!(cursor.remove_current(), None);
+    assert!(cursor.insert_after(1, "one".to_string()).is_ok());
+    assert_eq!(skiplist.get(&1), Some("one".to_string()));
+}
+
+#[test]
+fn test_insert_after() {
+    let mut skiplist = sample();
+
+    {
+        let mut cursor = skiplist.lower_bound_cursor_mut(&20);
+        assert!(cursor.insert_after(25, "value_25".to_string()).is_ok());
+
+        // The cursor stays put; the new entry is next
+        assert_eq!(cursor.key(), Some(20));
+        assert_eq!(cursor.peek_next(), Some((25, "value_25".to_string())));
+
+        // Keys outside (20, 25) are rejected
+        assert!(cursor.insert_after(20, "dup".to_string()).is_err());
+        assert!(cursor.insert_after(25, "dup".to_string()).is_err());
+        assert!(cursor.insert_after(30, "late".to_string()).is_err());
+        assert!(cursor.insert_after(5, "early".to_string()).is_err());
+    }
+
+    assert_eq!(skiplist.len(), 6);
+    assert_eq!(skiplist.get(&25), Some("value_25".to_string()));
+
+    // The new entry is reachable backwards too
+    let mut cursor = skiplist.lower_bound_cursor(&30);
+    cursor.move_prev();
+    assert_eq!(cursor.key(), Some(25));
+}
+
+#[test]
+fn test_insert_after_ghost_prepends() {
+    let mut skiplist = sample();
+
+    let mut cursor = skiplist.lower_bound_cursor_mut(&100);
+    assert!(cursor.is_ghost());
+    assert!(cursor.insert_after(60, "late".to_string()).is_err());
+    assert!(cursor.insert_after(5, "value_5".to_string()).is_ok());
+
+    assert_eq!(skiplist.cursor_front().key(), Some(5));
+}
+
+#[test]
+fn test_remove_current() {
+    let mut skiplist = sample();
+
+    {
+        let mut cursor = skiplist.lower_bound_cursor_mut(&30);
+        assert_eq!(cursor.remove_current(), Some((30, "value_30".to_string())));
+
+        // The cursor moves on to the next entry
+        assert_eq!(cursor.key(), Some(40));
+        assert_eq!(cursor.peek_prev(), Some((20, "value_20".to_string())));
+    }
+
+    assert_eq!(skiplist.len(), 4);
+    assert_eq!(skiplist.get(&30), None);
+    assert_eq!(skiplist.get(&40), Some("value_40".to_string()));
+}
+
+#[test]
+fn test_compaction_with_cursor() {
+    let mut skiplist = SkipList::new();
+    for i in 0..1000 {
+        skiplist.insert(i, i);
+    }
+
+    // Drop every multiple of 3 and double the rest in one pass
+    let mut cursor = skiplist.cursor_front_mut();
+    while let Some((key, value)) = cursor.current() {
+        if key % 3 == 0 {
+            cursor.remove_current();
+        } else {
+            cursor.set_value(value * 2);
+            cursor.move_next();
+        }
+    }
+
+    assert_eq!(skiplist.len(), 666);
+    for i in 0..1000 {
+        if i % 3 == 0 {
+            assert_eq!(skiplist.get(&i), None);
+        } else {
+            assert_eq!(skiplist.get(&i), Some(i * 2));
+        }
+    }
+
+    // Walking backwards from the end still sees every survivor
+    let mut cursor = skiplist.lower_bound_cursor(&1000);
+    assert!(cursor.is_ghost());
+    cursor.move_prev();
+    let mut count = 0;
+    while cursor.key().is_some() {
+        count += 1;
+        cursor.move_prev();
+    }
+    assert_eq!(count, 666);
+}
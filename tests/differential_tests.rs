@@ -0,0 +1,134 @@
+//! Differential tests: random operation sequences run against both a
+//! `SkipList` and a `BTreeMap`, which must agree on every observable result.
+//!
+//! Each case also draws the seed for the list's level generator, so tower
+//! heights are part of the case: proptest shrinks a failing case and records
+//! it under `proptest-regressions/`, and the next run replays the same
+//! operations on the same tower shapes first. Set `PROPTEST_CASES` to run
+//! more than the default 256 cases.
+
+use proptest::prelude::*;
+use skiplist_rs::SkipList;
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+/// Keys are drawn from a small range so that operations collide often
+const KEYS: i32 = 64;
+
+#[derive(Debug, Clone)]
+enum Op {
+    Insert(i32, u32),
+    Get(i32),
+    Remove(i32),
+    Range(Bound<i32>, Bound<i32>),
+    RangeRev(Bound<i32>, Bound<i32>),
+    Iter,
+    IterRev,
+}
+
+fn key() -> impl Strategy<Value = i32> {
+    // Reach a little past both ends to exercise misses at the edges
+    -2..KEYS + 2
+}
+
+fn bound() -> impl Strategy<Value = Bound<i32>> {
+    prop_oneof![
+        key().prop_map(Bound::Included),
+        key().prop_map(Bound::Excluded),
+        Just(Bound::Unbounded),
+    ]
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        4 => (key(), any::<u32>()).prop_map(|(k, v)| Op::Insert(k, v)),
+        2 => key().prop_map(Op::Get),
+        3 => key().prop_map(Op::Remove),
+        1 => (bound(), bound()).prop_map(|(a, b)| Op::Range(a, b)),
+        1 => (bound(), bound()).prop_map(|(a, b)| Op::RangeRev(a, b)),
+        1 => Just(Op::Iter),
+        1 => Just(Op::IterRev),
+    ]
+}
+
+/// Whether `BTreeMap::range` accepts the bounds (it panics on inverted ones)
+fn valid_range(start: Bound<i32>, end: Bound<i32>) -> bool {
+    match (start, end) {
+        (Bound::Included(a) | Bound::Excluded(a), Bound::Included(b) | Bound::Excluded(b)) if a > b => false,
+        (Bound::Excluded(a), Bound::Excluded(b)) if a == b => false,
+        _ => true,
+    }
+}
+
+/// Run `ops` against both maps, failing on the first disagreement
+fn run(skiplist: &mut SkipList<i32, u32>, ops: &[Op]) -> Result<(), TestCaseError> {
+    let mut model = BTreeMap::new();
+
+    for (step, op) in ops.iter().enumerate() {
+        match *op {
+            Op::Insert(k, v) => {
+                prop_assert_eq!(skiplist.insert(k, v), model.insert(k, v), "step {}: {:?}", step, op);
+            }
+            Op::Get(k) => {
+                prop_assert_eq!(skiplist.get(&k), model.get(&k).copied(), "step {}: {:?}", step, op);
+                prop_assert_eq!(skiplist.contains_key(&k), model.contains_key(&k), "step {}: {:?}", step, op);
+            }
+            Op::Remove(k) => {
+                prop_assert_eq!(skiplist.remove(&k), model.remove(&k), "step {}: {:?}", step, op);
+            }
+            Op::Range(start, end) => {
+                let actual: Vec<_> = skiplist.range((start, end)).collect();
+                let expected: Vec<_> = if valid_range(start, end) {
+                    model.range((start, end)).map(|(k, v)| (*k, *v)).collect()
+                } else {
+                    Vec::new()
+                };
+                prop_assert_eq!(actual, expected, "step {}: {:?}", step, op);
+            }
+            Op::RangeRev(start, end) => {
+                let actual: Vec<_> = skiplist.range((start, end)).rev().collect();
+                let expected: Vec<_> = if valid_range(start, end) {
+                    model.range((start, end)).rev().map(|(k, v)| (*k, *v)).collect()
+                } else {
+                    Vec::new()
+                };
+                prop_assert_eq!(actual, expected, "step {}: {:?}", step, op);
+            }
+            Op::Iter => {
+                let actual: Vec<_> = skiplist.iter().collect();
+                let expected: Vec<_> = model.iter().map(|(k, v)| (*k, *v)).collect();
+                prop_assert_eq!(actual, expected, "step {}: {:?}", step, op);
+            }
+            Op::IterRev => {
+                let actual: Vec<_> = skiplist.iter().rev().collect();
+                let expected: Vec<_> = model.iter().rev().map(|(k, v)| (*k, *v)).collect();
+                prop_assert_eq!(actual, expected, "step {}: {:?}", step, op);
+            }
+        }
+
+        prop_assert_eq!(skiplist.len(), model.len(), "step {}: {:?}", step, op);
+        prop_assert_eq!(skiplist.check_invariants(), Ok(()), "step {}: {:?}", step, op);
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn test_matches_btreemap(seed in any::<u64>(), ops in prop::collection::vec(op(), 0..200)) {
+        let mut skiplist = SkipList::new();
+        skiplist.seed(seed);
+        run(&mut skiplist, &ops)?;
+    }
+
+    #[test]
+    fn test_matches_btreemap_with_params(
+        initial_max in 1usize..6,
+        p in prop_oneof![Just(0.25), Just(0.5), Just(0.75)],
+        seed in any::<u64>(),
+        ops in prop::collection::vec(op(), 0..200),
+    ) {
+        let mut skiplist = SkipList::with_params(initial_max, p);
+        skiplist.seed(seed);
+        run(&mut skiplist, &ops)?;
+    }
+}
@@ -0,0 +1,160 @@
+use skiplist_rs::SkipList;
+
+fn numbered(n: i32) -> SkipList<i32, i32> {
+    let mut skiplist = SkipList::new();
+    for i in 0..n {
+        skiplist.insert(i, i * 10);
+    }
+    skiplist
+}
+
+fn keys(skiplist: &SkipList<i32, i32>) -> Vec<i32> {
+    skiplist.iter().map(|(k, _)| k).collect()
+}
+
+#[test]
+fn test_retain() {
+    let mut skiplist = numbered(100);
+
+    skiplist.retain(|k, _| k % 3 == 0);
+
+    assert_eq!(skiplist.len(), 34);
+    assert_eq!(keys(&skiplist), (0..100).step_by(3).collect::<Vec<_>>());
+    for i in 0..100 {
+        assert_eq!(skiplist.contains_key(&i), i % 3 == 0);
+    }
+
+    // Both directions stay consistent after the sweep
+    let rev: Vec<_> = skiplist.iter().rev().map(|(k, _)| k).collect();
+    assert_eq!(rev, (0..100).step_by(3).rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_retain_modifies_kept_values() {
+    let mut skiplist = numbered(10);
+
+    skiplist.retain(|k, v| {
+        *v += 1;
+        k % 2 == 1
+    });
+
+    assert_eq!(skiplist.get(&1), Some(11));
+    assert_eq!(skiplist.get(&9), Some(91));
+    assert_eq!(skiplist.get(&2), None);
+}
+
+#[test]
+fn test_retain_everything_and_nothing() {
+    let mut skiplist = numbered(50);
+
+    skiplist.retain(|_, _| true);
+    assert_eq!(skiplist.len(), 50);
+
+    skiplist.retain(|_, _| false);
+    assert!(skiplist.is_empty());
+    assert_eq!(skiplist.iter().next_back(), None);
+
+    // The list is still usable afterwards
+    skiplist.insert(7, 70);
+    assert_eq!(keys(&skiplist), vec![7]);
+}
+
+#[test]
+fn test_drain() {
+    let mut skiplist = numbered(20);
+
+    let drained: Vec<_> = skiplist.drain().collect();
+
+    assert_eq!(drained, (0..20).map(|i| (i, i * 10)).collect::<Vec<_>>());
+    assert!(skiplist.is_empty());
+    assert_eq!(skiplist.get(&5), None);
+}
+
+#[test]
+fn test_drain_dropped_early_still_empties() {
+    let mut skiplist = numbered(20);
+
+    let first: Vec<_> = skiplist.drain().take(3).collect();
+
+    assert_eq!(first, vec![(0, 0), (1, 10), (2, 20)]);
+    assert!(skiplist.is_empty());
+}
+
+#[test]
+fn test_drain_range() {
+    let mut skiplist = numbered(20);
+
+    let drained: Vec<_> = skiplist.drain_range(5..10).map(|(k, _)| k).collect();
+    assert_eq!(drained, vec![5, 6, 7, 8, 9]);
+    assert_eq!(skiplist.len(), 15);
+
+    let drained: Vec<_> = skiplist.drain_range(15..).map(|(k, _)| k).collect();
+    assert_eq!(drained, vec![15, 16, 17, 18, 19]);
+
+    let drained: Vec<_> = skiplist.drain_range(..=1).map(|(k, _)| k).collect();
+    assert_eq!(drained, vec![0, 1]);
+
+    assert_eq!(keys(&skiplist), vec![2, 3, 4, 10, 11, 12, 13, 14]);
+    let rev: Vec<_> = skiplist.iter().rev().map(|(k, _)| k).collect();
+    assert_eq!(rev, vec![14, 13, 12, 11, 10, 4, 3, 2]);
+}
+
+#[test]
+fn test_drain_range_bounds() {
+    use std::ops::Bound::{Excluded, Included};
+    let mut skiplist = numbered(10);
+
+    // Excluded start keeps the boundary key
+    let drained: Vec<_> = skiplist
+        .drain_range((Excluded(2), Included(5)))
+        .map(|(k, _)| k)
+        .collect();
+    assert_eq!(drained, vec![3, 4, 5]);
+    assert!(skiplist.contains_key(&2));
+    assert!(skiplist.contains_key(&6));
+
+    // Ranges with nothing in them remove nothing
+    assert_eq!(skiplist.drain_range(3..6).count(), 0);
+    assert_eq!(skiplist.drain_range(100..).count(), 0);
+    assert_eq!(skiplist.drain_range(7..7).count(), 0);
+    assert_eq!(skiplist.len(), 7);
+}
+
+#[test]
+fn test_extract_if() {
+    let mut skiplist = numbered(30);
+
+    let expired: Vec<_> = skiplist.extract_if(|_, v| *v >= 200).map(|(k, _)| k).collect();
+
+    assert_eq!(expired, (20..30).collect::<Vec<_>>());
+    assert_eq!(skiplist.len(), 20);
+    assert_eq!(skiplist.iter().next_back(), Some((19, 190)));
+}
+
+#[test]
+fn test_extract_if_stops_when_dropped() {
+    let mut skiplist = numbered(30);
+
+    // Only the first two matches are taken; the rest stay
+    let taken: Vec<_> = skiplist.extract_if(|k, _| k % 2 == 0).take(2).collect();
+
+    assert_eq!(taken, vec![(0, 0), (2, 20)]);
+    assert_eq!(skiplist.len(), 28);
+    assert!(skiplist.contains_key(&4));
+}
+
+#[test]
+fn test_bulk_removal_then_insert() {
+    let mut skiplist = numbered(200);
+
+    skiplist.retain(|k, _| k % 5 != 0);
+    for i in (0..200).step_by(5) {
+        assert_eq!(skiplist.insert(i, -i), None);
+    }
+
+    assert_eq!(skiplist.len(), 200);
+    for i in 0..200 {
+        let expected = if i % 5 == 0 { -i } else { i * 10 };
+        assert_eq!(skiplist.get(&i), Some(expected));
+    }
+}
@@ -0,0 +1,96 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use skiplist_rs::SkipList;
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+#[test]
+fn test_finger_ops_match_a_btreemap_while_the_list_changes() {
+    let mut rng = StdRng::seed_from_u64(50);
+    let mut skiplist = SkipList::new();
+    let mut model = BTreeMap::new();
+    let mut finger = skiplist.finger();
+    let mut cursor: i32 = 500;
+
+    for step in 0..20_000 {
+        // Mostly small moves, sometimes a jump
+        cursor = if rng.gen_bool(0.05) { rng.gen_range(0..1000) } else { (cursor + rng.gen_range(-8..=8)).clamp(0, 999) };
+        match rng.gen_range(0..10) {
+            0..=3 => assert_eq!(skiplist.insert_near(&mut finger, cursor, step), model.insert(cursor, step)),
+            4..=6 => assert_eq!(skiplist.get_near(&mut finger, &cursor), model.get(&cursor).copied()),
+            7 => {
+                let end = cursor + rng.gen_range(0..20);
+                let got: Vec<_> = skiplist.range_near(&mut finger, cursor..end).collect();
+                let expected: Vec<_> = model.range(cursor..end).map(|(k, v)| (*k, *v)).collect();
+                assert_eq!(got, expected, "range {}..{} at step {}", cursor, end, step);
+
+                let bounds = (Bound::Excluded(cursor), Bound::Included(end));
+                let got: Vec<_> = skiplist.range_near(&mut finger, bounds).rev().collect();
+                let expected: Vec<_> = model.range(bounds).rev().map(|(k, v)| (*k, *v)).collect();
+                assert_eq!(got, expected);
+            }
+            // Change the list behind the finger's back
+            8 => {
+                let key = (cursor + rng.gen_range(-20..=20)).clamp(0, 999);
+                assert_eq!(skiplist.remove(&key), model.remove(&key));
+            }
+            _ => {
+                let key = (cursor + rng.gen_range(-20..=20)).clamp(0, 999);
+                assert_eq!(skiplist.insert(key, step), model.insert(key, step));
+            }
+        }
+        if step % 1000 == 0 {
+            assert_eq!(skiplist.check_invariants(), Ok(()));
+        }
+    }
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+    assert_eq!(skiplist.iter().collect::<Vec<_>>(), model.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_finger_survives_removing_its_whole_path() {
+    let mut skiplist = SkipList::new();
+    for i in 0..100 {
+        skiplist.insert(i, i);
+    }
+    let mut finger = skiplist.finger();
+    assert_eq!(skiplist.get_near(&mut finger, &50), Some(50));
+
+    skiplist.retain(|k, _| *k >= 60);
+    assert_eq!(skiplist.get_near(&mut finger, &50), None);
+    assert_eq!(skiplist.get_near(&mut finger, &70), Some(70));
+    skiplist.insert_near(&mut finger, 55, 55);
+    assert_eq!(skiplist.range_near(&mut finger, ..61).collect::<Vec<_>>(), vec![(55, 55), (60, 60)]);
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_finger_restarts_on_another_or_split_list() {
+    let mut a = SkipList::new();
+    let mut b = SkipList::new();
+    for i in 0..100 {
+        a.insert(i, 'a');
+        b.insert(i, 'b');
+    }
+
+    let mut finger = a.finger();
+    assert_eq!(a.get_near(&mut finger, &90), Some('a'));
+    assert_eq!(b.get_near(&mut finger, &10), Some('b'));
+
+    // The finger's path now runs through nodes that move to `right`
+    b.get_near(&mut finger, &80);
+    let right = b.split_off(&50);
+    assert_eq!(b.get_near(&mut finger, &80), None);
+    b.insert_near(&mut finger, 70, 'x');
+    assert_eq!(b.check_invariants(), Ok(()));
+    assert_eq!(right.get(&70), Some('b'));
+
+    let mut c = SkipList::new();
+    c.insert(1000, 'c');
+    let mut finger = c.finger();
+    c.get_near(&mut finger, &1000);
+    a.append(&mut c);
+    c.insert_near(&mut finger, 5, 'c');
+    assert_eq!(c.iter().collect::<Vec<_>>(), vec![(5, 'c')]);
+    assert_eq!(a.get(&1000), Some('c'));
+}
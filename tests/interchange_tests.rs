@@ -0,0 +1,148 @@
+use skiplist_rs::{DuplicatePolicy, ExportOptions, Format, ImportErrorKind, ImportOptions, SkipList};
+
+fn csv() -> ImportOptions {
+    ImportOptions::default()
+}
+
+fn jsonl() -> ImportOptions {
+    ImportOptions { format: Format::JsonLines, ..Default::default() }
+}
+
+fn export_string<K: Ord + Clone + skiplist_rs::TextKey>(skiplist: &SkipList<K, String>, format: Format) -> String {
+    let mut out = Vec::new();
+    skiplist.export(&mut out, &ExportOptions { format, header: true }).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_csv_quoting_round_trip() {
+    let mut skiplist: SkipList<String, String> = SkipList::new();
+    skiplist.insert("plain".to_string(), "value".to_string());
+    skiplist.insert("comma, key".to_string(), "say \"hi\"".to_string());
+    skiplist.insert("multi".to_string(), "line one\r\nline two\n".to_string());
+    skiplist.insert("empty".to_string(), String::new());
+
+    let text = export_string(&skiplist, Format::Csv);
+    let mut copy: SkipList<String, String> = SkipList::new();
+    let summary = copy.import(text.as_bytes(), &csv()).unwrap();
+    assert_eq!(summary.inserted, 4);
+    assert!(copy.iter().eq(skiplist.iter()));
+}
+
+#[test]
+fn test_jsonl_round_trip_with_escapes() {
+    let mut skiplist: SkipList<String, String> = SkipList::new();
+    skiplist.insert("tab\tkey".to_string(), "quote \" backslash \\ bell \u{7} é 😀".to_string());
+    skiplist.insert("a".to_string(), "b".to_string());
+
+    let text = export_string(&skiplist, Format::JsonLines);
+    assert!(text.contains("\\t") && text.contains("\\u0007"));
+    let mut copy: SkipList<String, String> = SkipList::new();
+    copy.import(text.as_bytes(), &jsonl()).unwrap();
+    assert!(copy.iter().eq(skiplist.iter()));
+}
+
+#[test]
+fn test_jsonl_accepts_whitespace_numbers_and_unicode_escapes() {
+    let input = "{ \"value\" : 1.50 , \"key\" : -7 }\n\n  {\"key\":\"3\",\"value\":\"\\u00e9\\ud83d\\ude00\"}\n{\"key\":4,\"value\":true}\n";
+    let mut skiplist: SkipList<i64, String> = SkipList::new();
+    let summary = skiplist.import(input.as_bytes(), &jsonl()).unwrap();
+    assert_eq!(summary.records(), 3);
+    assert_eq!(skiplist.get(&-7), Some("1.50".to_string()));
+    assert_eq!(skiplist.get(&3), Some("é😀".to_string()));
+    assert_eq!(skiplist.get(&4), Some("true".to_string()));
+}
+
+#[test]
+fn test_byte_keys_are_hex() {
+    let input = "key,value\nff00,a\n0A,b\n";
+    let mut skiplist: SkipList<Vec<u8>, String> = SkipList::new();
+    skiplist.import(input.as_bytes(), &csv()).unwrap();
+    assert_eq!(skiplist.get(&vec![0xff, 0x00]), Some("a".to_string()));
+    assert_eq!(export_string(&skiplist, Format::Csv), "key,value\n0a,b\nff00,a\n");
+    assert_eq!(
+        export_string(&skiplist, Format::JsonLines),
+        "{\"key\":\"0a\",\"value\":\"b\"}\n{\"key\":\"ff00\",\"value\":\"a\"}\n"
+    );
+}
+
+#[test]
+fn test_duplicate_policies() {
+    let input = "k,v\n1,first\n2,two\n1,second\n";
+    let run = |duplicates| {
+        let mut skiplist: SkipList<u32, String> = SkipList::new();
+        let result = skiplist.import(input.as_bytes(), &ImportOptions { duplicates, ..Default::default() });
+        (result, skiplist.get(&1))
+    };
+
+    let (summary, value) = run(DuplicatePolicy::Overwrite);
+    assert_eq!((summary.unwrap().overwritten, value), (1, Some("second".to_string())));
+
+    let (summary, value) = run(DuplicatePolicy::KeepFirst);
+    assert_eq!((summary.unwrap().skipped, value), (1, Some("first".to_string())));
+
+    let (result, value) = run(DuplicatePolicy::Reject);
+    let err = result.unwrap_err();
+    assert_eq!((err.line, err.kind), (4, ImportErrorKind::Duplicate("1".to_string())));
+    // Records before the bad one stay in the list
+    assert_eq!(value, Some("first".to_string()));
+}
+
+#[test]
+fn test_header_option() {
+    let mut skiplist: SkipList<i64, String> = SkipList::new();
+    let options = ImportOptions { header: false, ..Default::default() };
+    skiplist.import("5,five\n6,six\n".as_bytes(), &options).unwrap();
+    assert_eq!(skiplist.len(), 2);
+
+    let mut out = Vec::new();
+    skiplist.export(&mut out, &ExportOptions { header: false, ..Default::default() }).unwrap();
+    assert_eq!(out, b"5,five\n6,six\n");
+}
+
+#[test]
+fn test_errors_report_the_starting_line() {
+    let cases: [(&str, ImportOptions, u64); 8] = [
+        ("k,v\n1,a\nx,b\n", csv(), 3),
+        ("k,v\n1,a\n2\n", csv(), 3),
+        ("k,v\n1,a,extra\n", csv(), 2),
+        ("k,v\n1,\"unterminated\n\n\n", csv(), 2),
+        ("k,v\n1,ab\"c\n", csv(), 2),
+        ("{\"key\":1}\n", jsonl(), 1),
+        ("{\"key\":1,\"value\":\"a\"}\n{\"key\":2,\"value\":null}\n", jsonl(), 2),
+        ("{\"key\":1,\"value\":\"a\",\"extra\":0}\n", jsonl(), 1),
+    ];
+    for (input, options, line) in cases {
+        let mut skiplist: SkipList<i64, String> = SkipList::new();
+        let err = skiplist.import(input.as_bytes(), &options).unwrap_err();
+        assert_eq!(err.line, line, "{:?}: {}", input, err);
+    }
+
+    let mut skiplist: SkipList<i64, String> = SkipList::new();
+    let err = skiplist.import("k,v\n99999999999999999999,a\n".as_bytes(), &csv()).unwrap_err();
+    assert!(matches!(err.kind, ImportErrorKind::InvalidKey(_)));
+    assert_eq!(err.to_string(), "line 2: invalid key: `99999999999999999999` is not a valid i64");
+}
+
+#[test]
+fn test_invalid_utf8_is_malformed() {
+    let mut skiplist: SkipList<i64, String> = SkipList::new();
+    let err = skiplist.import(&b"k,v\n1,\xff\n"[..], &csv()).unwrap_err();
+    assert_eq!(err.line, 2);
+    assert!(matches!(err.kind, ImportErrorKind::Malformed(_)));
+}
+
+#[test]
+fn test_streamed_snapshot_matches_in_memory() {
+    let mut skiplist: SkipList<i64, String> = SkipList::new();
+    let input: String = (0..1000).map(|i| format!("{},value {}\n", (i * 7919) % 1000, i)).collect();
+    skiplist.import(input.as_bytes(), &ImportOptions { header: false, ..Default::default() }).unwrap();
+
+    let mut streamed = Vec::new();
+    skiplist.write_snapshot(&mut streamed).unwrap();
+    assert_eq!(streamed, skiplist.to_snapshot());
+
+    let copy: SkipList<i64, String> = SkipList::read_snapshot(&streamed[..]).unwrap();
+    assert_eq!(copy.check_invariants(), Ok(()));
+    assert_eq!(copy.to_ascii(), skiplist.to_ascii());
+}
@@ -0,0 +1,109 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use skiplist_rs::{IntervalId, IntervalSkipList};
+use std::ops::RangeInclusive;
+
+/// Ids of `entries`, sorted
+fn ids<K, V>(entries: Vec<(IntervalId, &RangeInclusive<K>, &V)>) -> Vec<IntervalId> {
+    let mut ids: Vec<_> = entries.into_iter().map(|(id, _, _)| id).collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn test_queries_match_a_scan_through_random_edits() {
+    let mut rng = StdRng::seed_from_u64(46);
+    let mut list = IntervalSkipList::new();
+    list.seed(2);
+    let mut model: Vec<(IntervalId, RangeInclusive<i32>)> = Vec::new();
+
+    for step in 0..3000 {
+        if model.is_empty() || rng.gen_bool(0.6) {
+            let start = rng.gen_range(0..200);
+            let end = start + rng.gen_range(0..40);
+            let id = list.insert(start..=end, step);
+            model.push((id, start..=end));
+        } else {
+            let (id, range) = model.swap_remove(rng.gen_range(0..model.len()));
+            assert_eq!(list.remove(id).map(|(range, _)| range), Some(range));
+        }
+        assert_eq!(list.len(), model.len());
+
+        let point = rng.gen_range(-5..250);
+        let mut expected: Vec<_> = model.iter().filter(|(_, r)| r.contains(&point)).map(|(id, _)| *id).collect();
+        expected.sort();
+        assert_eq!(ids(list.stab(&point)), expected, "stab {} at step {}", point, step);
+
+        let (a, b) = (point, point + rng.gen_range(0..30));
+        let mut expected: Vec<_> = model
+            .iter()
+            .filter(|(_, r)| *r.start() <= b && a <= *r.end())
+            .map(|(id, _)| *id)
+            .collect();
+        expected.sort();
+        assert_eq!(ids(list.overlapping(&a, &b)), expected, "overlap [{}, {}] at step {}", a, b, step);
+    }
+}
+
+#[test]
+fn test_endpoints_are_inclusive() {
+    let mut list = IntervalSkipList::new();
+    let id = list.insert(10..=20, "a");
+    let point = list.insert(15..=15, "point");
+
+    assert_eq!(ids(list.stab(&10)), vec![id]);
+    assert_eq!(ids(list.stab(&20)), vec![id]);
+    assert!(list.stab(&9).is_empty());
+    assert!(list.stab(&21).is_empty());
+    assert_eq!(ids(list.stab(&15)), vec![id, point]);
+    assert_eq!(ids(list.overlapping(&20, &30)), vec![id]);
+    assert_eq!(ids(list.overlapping(&0, &10)), vec![id]);
+    assert!(list.overlapping(&21, &30).is_empty());
+    assert!(list.overlapping(&30, &0).is_empty());
+}
+
+#[test]
+fn test_duplicate_intervals_and_stale_ids() {
+    let mut list = IntervalSkipList::new();
+    let first = list.insert(1..=5, 'x');
+    let second = list.insert(1..=5, 'y');
+    assert_ne!(first, second);
+
+    assert_eq!(list.remove(first), Some((1..=5, 'x')));
+    assert_eq!(list.remove(first), None);
+    assert_eq!(list.get(first), None);
+    assert_eq!(list.get(second), Some((&(1..=5), &'y')));
+
+    // The freed slot is reused under a new id
+    let third = list.insert(2..=3, 'z');
+    assert_eq!(list.get(first), None);
+    assert_eq!(ids(list.stab(&3)), {
+        let mut both = vec![second, third];
+        both.sort();
+        both
+    });
+
+    list.remove(second);
+    list.remove(third);
+    assert!(list.is_empty());
+    assert!(list.stab(&3).is_empty());
+}
+
+#[test]
+fn test_results_are_ordered_by_start_then_end() {
+    let mut list = IntervalSkipList::new();
+    list.insert(5..=9, "c");
+    list.insert(0..=10, "b");
+    list.insert(0..=6, "a");
+
+    let names: Vec<_> = list.stab(&6).into_iter().map(|(_, _, name)| *name).collect();
+    assert_eq!(names, vec!["a", "b", "c"]);
+}
+
+#[test]
+#[should_panic(expected = "interval start is greater than its end")]
+fn test_insert_rejects_reversed_interval() {
+    let mut list = IntervalSkipList::new();
+    let (start, end) = (3, 1);
+    list.insert(start..=end, ());
+}
@@ -0,0 +1,98 @@
+use skiplist_rs::{InvariantError, SkipList};
+
+fn shuffled(n: i32) -> Vec<i32> {
+    // Deterministic scramble so the insert order isn't sorted
+    (0..n).map(|i| (i * 7919) % n).collect()
+}
+
+#[test]
+fn test_invariants_empty() {
+    let skiplist: SkipList<i32, String> = SkipList::new();
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_invariants_after_inserts_and_updates() {
+    let mut skiplist = SkipList::new();
+
+    for k in shuffled(1000) {
+        skiplist.insert(k, k);
+        assert_eq!(skiplist.check_invariants(), Ok(()));
+    }
+    for k in shuffled(1000).into_iter().take(100) {
+        skiplist.insert(k, -k);
+    }
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_invariants_after_removes() {
+    let mut skiplist = SkipList::new();
+    for k in shuffled(500) {
+        skiplist.insert(k, k);
+    }
+
+    for k in shuffled(500).into_iter().step_by(3) {
+        skiplist.remove(&k);
+        assert_eq!(skiplist.check_invariants(), Ok(()));
+    }
+}
+
+#[test]
+fn test_invariants_after_cursor_edits() {
+    let mut skiplist = SkipList::new();
+    for k in (0..200).step_by(2) {
+        skiplist.insert(k, k);
+    }
+
+    let mut cursor = skiplist.cursor_front_mut();
+    while let Some(key) = cursor.key() {
+        if key % 4 == 0 {
+            cursor.remove_current();
+        } else {
+            cursor.insert_after(key + 1, key + 1).unwrap();
+            cursor.move_next();
+            cursor.move_next();
+        }
+    }
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_invariants_after_bulk_operations() {
+    let mut skiplist = SkipList::new();
+    for k in shuffled(600) {
+        skiplist.insert(k, k);
+    }
+
+    skiplist.retain(|k, _| k % 5 != 0);
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+
+    skiplist.drain_range(100..200).for_each(drop);
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+
+    let mut right = skiplist.split_off(&300);
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+    assert_eq!(right.check_invariants(), Ok(()));
+
+    let mut other = SkipList::new();
+    for k in (0..600).step_by(5) {
+        other.insert(k, -k);
+    }
+    right.append(&mut other);
+    assert_eq!(right.check_invariants(), Ok(()));
+    assert_eq!(other.check_invariants(), Ok(()));
+
+    skiplist.append(&mut right);
+    assert_eq!(skiplist.check_invariants(), Ok(()));
+    assert_eq!(right.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_invariant_error_messages() {
+    let err = InvariantError::LengthMismatch { len: 3, counted: 2 };
+    assert_eq!(err.to_string(), "len is 3 but level 0 has 2 nodes");
+
+    let err = InvariantError::NotSubsequence { level: 2, index: 5 };
+    assert_eq!(err.to_string(), "node 5 at level 2 is missing from level 1");
+}
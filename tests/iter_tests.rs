@@ -0,0 +1,120 @@
+use skiplist_rs::SkipList;
+
+fn sample() -> SkipList<i32, String> {
+    let mut skiplist = SkipList::new();
+    for &i in &[50, 20, 80, 10, 30, 70, 90, 60, 40] {
+        skiplist.insert(i, format!("value_{}", i));
+    }
+    skiplist
+}
+
+fn keys<I: Iterator<Item = (i32, String)>>(iter: I) -> Vec<i32> {
+    iter.map(|(k, _)| k).collect()
+}
+
+#[test]
+fn test_iter_in_order() {
+    let skiplist = sample();
+
+    assert_eq!(keys(skiplist.iter()), vec![10, 20, 30, 40, 50, 60, 70, 80, 90]);
+    for (k, v) in &skiplist {
+        assert_eq!(v, format!("value_{}", k));
+    }
+}
+
+#[test]
+fn test_iter_rev() {
+    let skiplist = sample();
+
+    assert_eq!(keys(skiplist.iter().rev()), vec![90, 80, 70, 60, 50, 40, 30, 20, 10]);
+
+    // "Latest N entries"
+    assert_eq!(keys(skiplist.iter().rev().take(3)), vec![90, 80, 70]);
+}
+
+#[test]
+fn test_iter_both_ends_meet() {
+    let skiplist = sample();
+    let mut iter = skiplist.iter();
+
+    assert_eq!(iter.next().map(|(k, _)| k), Some(10));
+    assert_eq!(iter.next_back().map(|(k, _)| k), Some(90));
+    assert_eq!(iter.next().map(|(k, _)| k), Some(20));
+    assert_eq!(iter.next_back().map(|(k, _)| k), Some(80));
+
+    // The remaining middle is yielded exactly once
+    assert_eq!(keys(iter.by_ref()), vec![30, 40, 50, 60, 70]);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
+
+#[test]
+fn test_iter_empty() {
+    let skiplist: SkipList<i32, String> = SkipList::new();
+
+    assert_eq!(skiplist.iter().next(), None);
+    assert_eq!(skiplist.iter().next_back(), None);
+    assert_eq!(skiplist.range(..).next(), None);
+}
+
+#[test]
+fn test_range_bounds() {
+    let skiplist = sample();
+
+    assert_eq!(keys(skiplist.range(30..60)), vec![30, 40, 50]);
+    assert_eq!(keys(skiplist.range(30..=60)), vec![30, 40, 50, 60]);
+    assert_eq!(keys(skiplist.range(25..65)), vec![30, 40, 50, 60]);
+    assert_eq!(keys(skiplist.range(..30)), vec![10, 20]);
+    assert_eq!(keys(skiplist.range(75..)), vec![80, 90]);
+    assert_eq!(keys(skiplist.range(..)).len(), 9);
+
+    // Excluded start bound
+    use std::ops::Bound::{Excluded, Included};
+    assert_eq!(keys(skiplist.range((Excluded(30), Included(50)))), vec![40, 50]);
+}
+
+#[test]
+fn test_range_empty() {
+    let skiplist = sample();
+
+    assert_eq!(keys(skiplist.range(31..40)), Vec::<i32>::new()); // Gap between keys
+    assert_eq!(keys(skiplist.range(91..)), Vec::<i32>::new()); // After all keys
+    assert_eq!(keys(skiplist.range(..10)), Vec::<i32>::new()); // Before all keys
+    assert_eq!(keys(skiplist.range(30..30)), Vec::<i32>::new()); // Empty range
+    #[allow(clippy::reversed_empty_ranges)]
+    let backwards = skiplist.range(60..30);
+    assert_eq!(keys(backwards), Vec::<i32>::new());
+}
+
+#[test]
+fn test_range_rev() {
+    let skiplist = sample();
+
+    assert_eq!(keys(skiplist.range(20..=50).rev()), vec![50, 40, 30, 20]);
+    assert_eq!(keys(skiplist.range(..=35).rev()), vec![30, 20, 10]);
+}
+
+#[test]
+fn test_rev_after_remove_and_insert() {
+    let mut skiplist = sample();
+
+    // Removing the tail and head keeps both ends reachable
+    skiplist.remove(&90);
+    skiplist.remove(&10);
+    skiplist.remove(&50);
+    assert_eq!(keys(skiplist.iter().rev()), vec![80, 70, 60, 40, 30, 20]);
+
+    // New largest and smallest keys become the new ends
+    skiplist.insert(100, "value_100".to_string());
+    skiplist.insert(5, "value_5".to_string());
+    assert_eq!(keys(skiplist.iter().rev()), vec![100, 80, 70, 60, 40, 30, 20, 5]);
+    assert_eq!(keys(skiplist.iter()), vec![5, 20, 30, 40, 60, 70, 80, 100]);
+
+    // Emptying the list clears the tail
+    for k in keys(skiplist.iter()) {
+        skiplist.remove(&k);
+    }
+    assert_eq!(skiplist.iter().next_back(), None);
+    skiplist.insert(1, "value_1".to_string());
+    assert_eq!(keys(skiplist.iter().rev()), vec![1]);
+}
@@ -0,0 +1,250 @@
+//! Loom model tests for the concurrent paths.
+//!
+//! Run with:
+//!
+//! ```text
+//! RUSTFLAGS="--cfg loom" cargo test --release --test loom_tests
+//! ```
+//!
+//! With `--cfg loom` the node locks inside the list are loom locks, so every
+//! node lock taken by `get`, `range` and `iter` is a point where loom may
+//! switch threads.
+//!
+//! Writers take `&mut self`, so through the public API they are always
+//! serialized: the list-level models that mutate wrap it in an outer loom
+//! `Mutex` or `RwLock`, and explore whole operations against each other and
+//! reader/reader interleavings on the node locks. The lock ordering inside
+//! `insert` and `remove` is modelled separately at the node level:
+//! `test_link_and_unlink_lock_sequences_race` runs the same predecessor,
+//! node, successor sequence `link_after` and `unlink` use, from two threads
+//! over overlapping nodes. An acquisition out of that order, such as keeping
+//! one level's predecessor locked while taking the next level's, shows up as
+//! a reported deadlock. Lists are seeded so tower heights, and with them the sequence
+//! of lock operations, are the same on every explored execution.
+#![cfg(loom)]
+
+use loom::sync::{Arc, Mutex, RwLock};
+use loom::thread;
+use skiplist_rs::SkipList;
+
+/// A small seeded list holding `keys`
+fn seeded(keys: &[i32]) -> SkipList<i32, i32> {
+    let mut skiplist = SkipList::with_params(2, 0.5);
+    skiplist.seed(7);
+    for &key in keys {
+        skiplist.insert(key, key * 10);
+    }
+    skiplist
+}
+
+/// Explore interleavings with a bounded number of preemptions per execution
+fn model<F>(f: F)
+where
+    F: Fn() + Sync + Send + 'static,
+{
+    let mut builder = loom::model::Builder::new();
+    builder.preemption_bound = Some(3);
+    builder.check(f);
+}
+
+#[test]
+fn test_rwlock_insert_races_get() {
+    model(|| {
+        let list = Arc::new(RwLock::new(seeded(&[1, 3])));
+
+        let writer = {
+            let list = Arc::clone(&list);
+            thread::spawn(move || {
+                list.write().unwrap().insert(2, 20);
+            })
+        };
+        let seen = list.read().unwrap().get(&2);
+        writer.join().unwrap();
+
+        assert!(seen.is_none() || seen == Some(20));
+        let list = list.read().unwrap();
+        assert_eq!(list.get(&2), Some(20));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.check_invariants(), Ok(()));
+    });
+}
+
+#[test]
+fn test_rwlock_remove_races_get() {
+    model(|| {
+        let list = Arc::new(RwLock::new(seeded(&[1, 2, 3])));
+
+        let writer = {
+            let list = Arc::clone(&list);
+            thread::spawn(move || list.write().unwrap().remove(&2))
+        };
+        let seen = list.read().unwrap().get(&2);
+        let removed = writer.join().unwrap();
+
+        assert_eq!(removed, Some(20));
+        assert!(seen.is_none() || seen == Some(20));
+        let list = list.read().unwrap();
+        assert_eq!(list.get(&2), None);
+        assert_eq!(list.check_invariants(), Ok(()));
+    });
+}
+
+#[test]
+fn test_mutex_inserts_sharing_predecessors() {
+    model(|| {
+        // Both new keys land between 1 and 4, so the inserts relink the same
+        // predecessor towers
+        let list = Arc::new(Mutex::new(seeded(&[1, 4])));
+
+        let handles: Vec<_> = [2, 3]
+            .into_iter()
+            .map(|key| {
+                let list = Arc::clone(&list);
+                thread::spawn(move || {
+                    list.lock().unwrap().insert(key, key * 10);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let list = list.lock().unwrap();
+        let keys: Vec<_> = list.iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, vec![1, 2, 3, 4]);
+        assert_eq!(list.check_invariants(), Ok(()));
+    });
+}
+
+#[test]
+fn test_mutex_insert_races_remove_of_same_key() {
+    model(|| {
+        let list = Arc::new(Mutex::new(seeded(&[1, 2, 3])));
+
+        let inserter = {
+            let list = Arc::clone(&list);
+            thread::spawn(move || list.lock().unwrap().insert(2, 200))
+        };
+        let removed = list.lock().unwrap().remove(&2);
+        let replaced = inserter.join().unwrap();
+
+        // Whichever ran first, the final state is consistent with that order
+        let list = list.lock().unwrap();
+        match (removed, replaced) {
+            (Some(20), None) => assert_eq!(list.get(&2), Some(200)),
+            (Some(200), Some(20)) => assert_eq!(list.get(&2), None),
+            other => panic!("impossible interleaving: {:?}", other),
+        }
+        assert_eq!(list.check_invariants(), Ok(()));
+    });
+}
+
+#[test]
+fn test_shared_readers_take_node_locks_concurrently() {
+    model(|| {
+        // No outer lock: both threads search the same towers at once
+        let list = Arc::new(seeded(&[1, 2, 3]));
+
+        let reader = {
+            let list = Arc::clone(&list);
+            thread::spawn(move || {
+                assert_eq!(list.get(&3), Some(30));
+                assert_eq!(list.range(2..).count(), 2);
+            })
+        };
+        assert_eq!(list.get(&1), Some(10));
+        assert_eq!(list.iter().rev().map(|(k, _)| k).collect::<Vec<_>>(), vec![3, 2, 1]);
+        reader.join().unwrap();
+    });
+}
+
+/// A bare node for the lock-sequence model: the key, forward links and the
+/// key of the level-0 predecessor
+struct Bare {
+    key: i32,
+    fwd: Vec<Option<Arc<RwLock<Bare>>>>,
+    back: i32,
+}
+
+type BareRef = Arc<RwLock<Bare>>;
+
+fn bare(key: i32, height: usize) -> BareRef {
+    Arc::new(RwLock::new(Bare { key, fwd: vec![None; height], back: 0 }))
+}
+
+/// `link_after`'s lock sequence: predecessor, new node, then at level 0 the
+/// successor for its back link
+fn link_after(update: &[BareRef], new_node: &BareRef) {
+    for (level, pred) in update.iter().enumerate() {
+        let mut prev = pred.write().unwrap();
+        let mut node = new_node.write().unwrap();
+        node.fwd[level] = prev.fwd[level].take();
+        prev.fwd[level] = Some(Arc::clone(new_node));
+
+        if level == 0 {
+            node.back = prev.key;
+            if let Some(next) = &node.fwd[0] {
+                next.write().unwrap().back = node.key;
+            }
+        }
+    }
+}
+
+/// `unlink`'s lock sequence: predecessor, node, then at level 0 the successor
+fn unlink(update: &[BareRef], node: &BareRef) {
+    let height = node.read().unwrap().fwd.len();
+    for (level, pred) in update.iter().enumerate().take(height) {
+        let mut prev = pred.write().unwrap();
+        prev.fwd[level] = node.write().unwrap().fwd[level].take();
+
+        if level == 0 {
+            if let Some(next) = &prev.fwd[0] {
+                next.write().unwrap().back = prev.key;
+            }
+        }
+    }
+}
+
+/// Keys along `level` from `start`, with each level-0 node's back link
+fn walk(start: &BareRef, level: usize) -> Vec<(i32, i32)> {
+    let mut keys = Vec::new();
+    let mut next = start.read().unwrap().fwd[level].clone();
+    while let Some(node) = next {
+        let node = node.read().unwrap();
+        keys.push((node.key, node.back));
+        next = node.fwd[level].clone();
+    }
+    keys
+}
+
+#[test]
+fn test_link_and_unlink_lock_sequences_race() {
+    model(|| {
+        // Level 1: H -> 1 ---------> 4
+        // Level 0: H -> 1 -> 3 -> 4 -> 5
+        let head = bare(0, 2);
+        let nodes: Vec<_> = [(1, 2), (3, 1), (4, 2), (5, 1)].into_iter().map(|(k, h)| bare(k, h)).collect();
+        let chain = |level: usize, path: &[&BareRef]| {
+            for pair in path.windows(2) {
+                pair[0].write().unwrap().fwd[level] = Some(Arc::clone(pair[1]));
+            }
+        };
+        chain(0, &[&head, &nodes[0], &nodes[1], &nodes[2], &nodes[3]]);
+        chain(1, &[&head, &nodes[0], &nodes[2]]);
+        for pair in nodes.windows(2) {
+            pair[1].write().unwrap().back = pair[0].read().unwrap().key;
+        }
+
+        // Inserting 2 locks 1, 2 and 3; removing 4 locks 3, 4 and 5 at level
+        // 0, then 1 and 4 at level 1
+        let inserter = {
+            let pred = Arc::clone(&nodes[0]);
+            thread::spawn(move || link_after(&[pred], &bare(2, 1)))
+        };
+        unlink(&[Arc::clone(&nodes[1]), Arc::clone(&nodes[0])], &nodes[2]);
+        inserter.join().unwrap();
+
+        assert_eq!(walk(&head, 0), vec![(1, 0), (2, 1), (3, 2), (5, 3)]);
+        assert_eq!(walk(&head, 1), vec![(1, 0)]);
+    });
+}
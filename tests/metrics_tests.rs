@@ -0,0 +1,133 @@
+#![cfg(feature = "metrics")]
+
+use skiplist_rs::SkipList;
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+#[test]
+fn test_fresh_list_has_zero_counters() {
+    let skiplist: SkipList<i32, i32> = SkipList::new();
+    let metrics = skiplist.metrics();
+
+    assert_eq!(metrics.inserts(), 0);
+    assert_eq!(metrics.updates(), 0);
+    assert_eq!(metrics.hits(), 0);
+    assert_eq!(metrics.misses(), 0);
+    assert_eq!(metrics.removes(), 0);
+    assert_eq!(metrics.resizes(), 0);
+    assert_eq!(metrics.lock_contention(), 0);
+}
+
+#[test]
+fn test_insert_update_and_lookup_counters() {
+    let mut skiplist = SkipList::new();
+    for i in 0..10 {
+        skiplist.insert(i, i);
+    }
+    skiplist.insert(3, 30);
+    skiplist.insert(4, 40);
+
+    for key in 0..15 {
+        skiplist.get(&key);
+    }
+    assert!(skiplist.contains_key(&9));
+
+    let metrics = skiplist.metrics();
+    assert_eq!(metrics.inserts(), 10);
+    assert_eq!(metrics.updates(), 2);
+    assert_eq!(metrics.hits(), 11);
+    assert_eq!(metrics.misses(), 5);
+}
+
+#[test]
+fn test_remove_counters_cover_bulk_removal() {
+    let mut skiplist = SkipList::new();
+    for i in 0..20 {
+        skiplist.insert(i, i);
+    }
+
+    skiplist.remove(&0);
+    skiplist.remove(&0);
+    skiplist.retain(|k, _| k % 2 == 0);
+    skiplist.drain_range(10..).for_each(drop);
+
+    // 1 single remove, 10 odd keys, then 10, 12, .., 18
+    assert_eq!(skiplist.metrics().removes(), 16);
+    assert_eq!(skiplist.len(), 4);
+}
+
+#[test]
+fn test_resizes_count_head_growth() {
+    let mut skiplist = SkipList::with_params(1, 0.5);
+    for i in 0..1000 {
+        skiplist.insert(i, i);
+    }
+
+    // Growing from 1 level to ceil(log2 1000) + 2 = 12 takes several steps
+    let resizes = skiplist.metrics().resizes();
+    assert!(resizes > 1);
+    assert!(resizes <= 11);
+    assert_eq!(skiplist.stats().max_level, 12);
+}
+
+#[test]
+fn test_append_keeps_each_lists_metrics() {
+    let mut a = SkipList::new();
+    let mut b = SkipList::new();
+    for i in 0..5 {
+        b.insert(i, i);
+    }
+
+    a.append(&mut b);
+    assert_eq!(a.len(), 5);
+    assert_eq!(a.metrics().inserts(), 0);
+    assert_eq!(b.metrics().inserts(), 5);
+}
+
+#[test]
+fn test_concurrent_readers_share_counters() {
+    let mut skiplist = SkipList::new();
+    for i in 0..100 {
+        skiplist.insert(i, i);
+    }
+    let skiplist = Arc::new(RwLock::new(skiplist));
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let skiplist = Arc::clone(&skiplist);
+            thread::spawn(move || {
+                let list = skiplist.read().unwrap();
+                for key in 0..200 {
+                    list.get(&key);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let list = skiplist.read().unwrap();
+    assert_eq!(list.metrics().hits(), 400);
+    assert_eq!(list.metrics().misses(), 400);
+}
+
+#[test]
+fn test_prometheus_exposition() {
+    let mut skiplist = SkipList::new();
+    skiplist.insert(1, "one");
+    skiplist.get(&1);
+    skiplist.get(&2);
+
+    let text = skiplist.metrics().to_prometheus(&[]);
+    assert!(text.contains("# HELP skiplist_inserts_total "));
+    assert!(text.contains("# TYPE skiplist_misses_total counter\n"));
+    assert!(text.contains("skiplist_hits_total 1\n"));
+    assert!(text.contains("skiplist_misses_total 1\n"));
+    assert_eq!(text.lines().count(), 7 * 3);
+
+    let labelled = skiplist
+        .metrics()
+        .to_prometheus(&[("list", "a \"quoted\" name"), ("shard", "0")]);
+    assert!(labelled.contains("skiplist_inserts_total{list=\"a \\\"quoted\\\" name\",shard=\"0\"} 1\n"));
+}
@@ -0,0 +1,147 @@
+use skiplist_rs::PersistentSkipList;
+
+#[test]
+fn test_insert_and_get() {
+    let list = PersistentSkipList::new()
+        .insert(2, "two")
+        .insert(1, "one")
+        .insert(3, "three");
+
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.get(&1), Some(&"one"));
+    assert_eq!(list.get(&2), Some(&"two"));
+    assert_eq!(list.get(&3), Some(&"three"));
+    assert_eq!(list.get(&4), None);
+}
+
+#[test]
+fn test_old_version_survives_insert() {
+    let empty: PersistentSkipList<i32, String> = PersistentSkipList::new();
+    let v1 = empty.insert(1, "one".to_string());
+    let v2 = v1.insert(2, "two".to_string());
+
+    // Each version only sees its own entries
+    assert!(empty.is_empty());
+    assert_eq!(v1.len(), 1);
+    assert_eq!(v1.get(&2), None);
+    assert_eq!(v2.len(), 2);
+    assert_eq!(v2.get(&1), Some(&"one".to_string()));
+    assert_eq!(v2.get(&2), Some(&"two".to_string()));
+}
+
+#[test]
+fn test_old_version_survives_update() {
+    let v1 = PersistentSkipList::new().insert(42, "initial");
+    let v2 = v1.insert(42, "updated");
+
+    assert_eq!(v1.len(), 1);
+    assert_eq!(v2.len(), 1); // Size should remain the same
+    assert_eq!(v1.get(&42), Some(&"initial"));
+    assert_eq!(v2.get(&42), Some(&"updated"));
+}
+
+#[test]
+fn test_old_version_survives_remove() {
+    let mut list = PersistentSkipList::new();
+    for i in 0..100 {
+        list = list.insert(i, i * 10);
+    }
+    let removed = list.remove(&50).remove(&0).remove(&99);
+
+    assert_eq!(list.len(), 100);
+    assert_eq!(removed.len(), 97);
+    for i in 0..100 {
+        assert_eq!(list.get(&i), Some(&(i * 10)));
+    }
+    assert_eq!(removed.get(&0), None);
+    assert_eq!(removed.get(&50), None);
+    assert_eq!(removed.get(&99), None);
+    assert_eq!(removed.get(&49), Some(&490));
+}
+
+#[test]
+fn test_remove_missing_key_shares_version() {
+    let list = PersistentSkipList::new().insert(1, "one");
+    let same = list.remove(&2);
+
+    assert!(same.ptr_eq(&list));
+    assert_eq!(same.len(), 1);
+}
+
+#[test]
+fn test_clone_is_shared() {
+    let list = PersistentSkipList::new().insert(1, "one").insert(2, "two");
+    let copy = list.clone();
+
+    assert!(copy.ptr_eq(&list));
+    assert!(!copy.insert(3, "three").ptr_eq(&list));
+}
+
+#[test]
+fn test_diverging_versions() {
+    let mut base = PersistentSkipList::new();
+    for i in (0..200).step_by(2) {
+        base = base.insert(i, format!("base_{}", i));
+    }
+
+    // Two branches evolve independently from the same base
+    let mut left = base.clone();
+    let mut right = base.clone();
+    for i in (1..200).step_by(2) {
+        left = left.insert(i, format!("left_{}", i));
+    }
+    for i in (0..200).step_by(4) {
+        right = right.remove(&i);
+    }
+
+    assert_eq!(base.len(), 100);
+    assert_eq!(left.len(), 200);
+    assert_eq!(right.len(), 50);
+
+    let base_keys: Vec<_> = base.iter().map(|(k, _)| *k).collect();
+    assert_eq!(base_keys, (0..200).step_by(2).collect::<Vec<_>>());
+
+    let left_keys: Vec<_> = left.iter().map(|(k, _)| *k).collect();
+    assert_eq!(left_keys, (0..200).collect::<Vec<_>>());
+
+    let right_keys: Vec<_> = right.iter().map(|(k, _)| *k).collect();
+    assert_eq!(right_keys, (2..200).step_by(4).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_iter_ordering() {
+    let values = vec![50, 20, 80, 10, 30, 70, 90, 5, 15, 25, 35, 60, 75, 85, 95];
+    let mut list = PersistentSkipList::new();
+    for &val in &values {
+        list = list.insert(val, format!("value_{}", val));
+    }
+
+    let mut sorted = values.clone();
+    sorted.sort();
+    let keys: Vec<_> = list.iter().map(|(k, _)| *k).collect();
+    assert_eq!(keys, sorted);
+    assert_eq!(list.iter().len(), values.len());
+
+    for (k, v) in list.iter() {
+        assert_eq!(v, &format!("value_{}", k));
+    }
+}
+
+#[test]
+fn test_remove_everything() {
+    let mut list = PersistentSkipList::new();
+    for i in 0..500 {
+        list = list.insert(i, i);
+    }
+    let full = list.clone();
+
+    // Remove in an order that exercises both ends and the middle
+    for i in (0..500).rev().step_by(2).chain((0..500).step_by(2)) {
+        list = list.remove(&i);
+    }
+
+    assert!(list.is_empty());
+    assert_eq!(list.iter().count(), 0);
+    assert_eq!(full.len(), 500);
+    assert_eq!(full.iter().count(), 500);
+}
@@ -0,0 +1,62 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use skiplist_rs::SkipList;
+
+/// A random short key over a small alphabet, so prefixes collide often
+fn random_key(rng: &mut StdRng) -> String {
+    let len = rng.gen_range(0..6);
+    (0..len).map(|_| ['a', 'b', '/'][rng.gen_range(0..3)]).collect()
+}
+
+#[test]
+fn test_prefix_iter_matches_a_filter() {
+    let mut rng = StdRng::seed_from_u64(48);
+    let mut skiplist = SkipList::new();
+    for i in 0..300 {
+        skiplist.insert(random_key(&mut rng), i);
+    }
+
+    for _ in 0..200 {
+        let prefix = random_key(&mut rng);
+        let expected: Vec<_> = skiplist.iter().filter(|(k, _)| k.starts_with(&prefix)).collect();
+        assert_eq!(skiplist.prefix_iter(&prefix).collect::<Vec<_>>(), expected, "prefix {:?}", prefix);
+    }
+}
+
+#[test]
+fn test_longest_prefix_match_matches_a_scan() {
+    let mut rng = StdRng::seed_from_u64(49);
+    let mut skiplist = SkipList::new();
+    for i in 0..40 {
+        skiplist.insert(random_key(&mut rng), i);
+    }
+
+    for _ in 0..500 {
+        let query = random_key(&mut rng) + &random_key(&mut rng);
+        let expected = skiplist.iter().filter(|(k, _)| query.starts_with(k.as_str())).max_by_key(|(k, _)| k.len());
+        assert_eq!(skiplist.longest_prefix_match(&query), expected, "query {:?}", query);
+    }
+}
+
+#[test]
+fn test_byte_keys() {
+    let mut skiplist: SkipList<Vec<u8>, u8> = SkipList::new();
+    skiplist.insert(vec![10, 0], 1);
+    skiplist.insert(vec![10, 0, 0], 2);
+    skiplist.insert(vec![10, 1], 3);
+    skiplist.insert(vec![192, 168], 4);
+
+    let tens: Vec<_> = skiplist.prefix_iter([10u8, 0]).map(|(_, v)| v).collect();
+    assert_eq!(tens, vec![1, 2]);
+    assert_eq!(skiplist.longest_prefix_match([10u8, 0, 0, 7]), Some((vec![10, 0, 0], 2)));
+    assert_eq!(skiplist.longest_prefix_match([10u8, 0, 1]), Some((vec![10, 0], 1)));
+    assert_eq!(skiplist.longest_prefix_match([10u8]), None);
+    assert_eq!(skiplist.prefix_iter([]).count(), 4);
+}
+
+#[test]
+fn test_empty_list() {
+    let skiplist: SkipList<String, i32> = SkipList::new();
+    assert_eq!(skiplist.prefix_iter("a").next(), None);
+    assert_eq!(skiplist.longest_prefix_match("abc"), None);
+}
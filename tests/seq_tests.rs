@@ -0,0 +1,94 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use skiplist_rs::SkipSeq;
+
+#[test]
+fn test_edits_match_a_vec() {
+    let mut rng = StdRng::seed_from_u64(47);
+    let mut seq = SkipSeq::new();
+    seq.seed(3);
+    let mut model = Vec::new();
+
+    for step in 0..5000 {
+        if model.is_empty() || rng.gen_bool(0.6) {
+            let index = rng.gen_range(0..=model.len());
+            seq.insert_at(index, step);
+            model.insert(index, step);
+        } else {
+            let index = rng.gen_range(0..model.len());
+            assert_eq!(seq.remove_at(index), Some(model.remove(index)));
+        }
+        assert_eq!(seq.len(), model.len());
+
+        let probe = rng.gen_range(0..=model.len());
+        assert_eq!(seq.get(probe), model.get(probe).copied(), "get {} at step {}", probe, step);
+    }
+    assert_eq!(seq.iter().collect::<Vec<_>>(), model);
+}
+
+#[test]
+fn test_split_and_concat_match_a_vec() {
+    let mut rng = StdRng::seed_from_u64(48);
+    let mut seq: SkipSeq<u32> = (0..2000).collect();
+    let mut model: Vec<u32> = (0..2000).collect();
+
+    for _ in 0..200 {
+        let index = rng.gen_range(0..=model.len());
+        let right = seq.split_at(index);
+        let right_model = model.split_off(index);
+        assert_eq!(seq.len(), model.len());
+        assert_eq!(right.len(), right_model.len());
+        assert_eq!(right.get(0), right_model.first().copied());
+
+        // Rejoin in the other order, with an edit on each side
+        let mut joined = right;
+        let mut joined_model = right_model;
+        let at = rng.gen_range(0..=joined_model.len());
+        joined.insert_at(at, 9999);
+        joined_model.insert(at, 9999);
+        joined.concat(std::mem::take(&mut seq));
+        joined_model.append(&mut model);
+
+        seq = joined;
+        model = joined_model;
+        let probe = rng.gen_range(0..model.len());
+        assert_eq!(seq.get(probe), Some(model[probe]));
+        assert_eq!(seq.remove_at(probe), Some(model.remove(probe)));
+    }
+    assert_eq!(seq.iter().collect::<Vec<_>>(), model);
+}
+
+#[test]
+fn test_split_at_the_ends() {
+    let mut seq: SkipSeq<char> = "abc".chars().collect();
+
+    let all = seq.split_at(0);
+    assert!(seq.is_empty());
+    assert_eq!(all.iter().collect::<String>(), "abc");
+
+    let mut seq = all;
+    let none = seq.split_at(3);
+    assert!(none.is_empty());
+    assert_eq!(seq.len(), 3);
+
+    seq.concat(none);
+    seq.concat(SkipSeq::new());
+    assert_eq!(seq.iter().collect::<String>(), "abc");
+    assert_eq!(seq.remove_at(3), None);
+    assert_eq!(seq.get(3), None);
+}
+
+#[test]
+fn test_long_sequence_drops_without_overflowing() {
+    let seq: SkipSeq<u32> = (0..200_000).collect();
+    assert_eq!(seq.get(199_999), Some(199_999));
+    drop(seq);
+}
+
+#[test]
+#[should_panic(expected = "insertion index (is 2) should be <= len (is 1)")]
+fn test_insert_past_the_end_panics() {
+    let mut seq = SkipSeq::new();
+    seq.push(1);
+    seq.insert_at(2, 2);
+}
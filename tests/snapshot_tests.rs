@@ -0,0 +1,122 @@
+use skiplist_rs::{SkipList, SnapshotError};
+
+fn numbered(n: u32) -> SkipList<u32, String> {
+    let mut skiplist = SkipList::new();
+    for i in 0..n {
+        skiplist.insert(i * 3, format!("val-{}", i));
+    }
+    skiplist
+}
+
+/// Header for a snapshot of `u32` keys and values with the given fields
+fn header(levels: u8, len: u64) -> Vec<u8> {
+    let mut bytes = b"SKPL".to_vec();
+    bytes.push(1);
+    bytes.extend_from_slice(&0.5f64.to_le_bytes());
+    bytes.push(levels);
+    bytes.extend_from_slice(&len.to_le_bytes());
+    bytes
+}
+
+fn entry(bytes: &mut Vec<u8>, height: u8, key: u32, val: u32) {
+    bytes.push(height);
+    bytes.extend_from_slice(&key.to_le_bytes());
+    bytes.extend_from_slice(&val.to_le_bytes());
+}
+
+#[test]
+fn test_round_trip_preserves_shape() {
+    let mut skiplist = numbered(500);
+    skiplist.remove(&30);
+
+    let copy: SkipList<u32, String> = SkipList::from_snapshot(&skiplist.to_snapshot()).unwrap();
+    assert_eq!(copy.check_invariants(), Ok(()));
+    assert_eq!(copy.len(), skiplist.len());
+    assert!(copy.iter().eq(skiplist.iter()));
+    assert_eq!(copy.stats().nodes_per_level, skiplist.stats().nodes_per_level);
+    assert_eq!(copy.to_ascii(), skiplist.to_ascii());
+}
+
+#[test]
+fn test_round_trip_empty_and_bytes() {
+    let empty: SkipList<i64, Vec<u8>> = SkipList::new();
+    let copy = SkipList::<i64, Vec<u8>>::from_snapshot(&empty.to_snapshot()).unwrap();
+    assert!(copy.is_empty());
+
+    let mut skiplist = SkipList::new();
+    skiplist.insert(-5i64, vec![0u8, 255, 7]);
+    skiplist.insert(9, Vec::new());
+    let copy = SkipList::<i64, Vec<u8>>::from_snapshot(&skiplist.to_snapshot()).unwrap();
+    assert_eq!(copy.get(&-5), Some(vec![0, 255, 7]));
+    assert_eq!(copy.get(&9), Some(Vec::new()));
+}
+
+#[test]
+fn test_every_truncation_is_an_error() {
+    let bytes = numbered(50).to_snapshot();
+    for end in 0..bytes.len() {
+        let result = SkipList::<u32, String>::from_snapshot(&bytes[..end]);
+        assert!(result.is_err(), "prefix of {} bytes loaded", end);
+    }
+}
+
+#[test]
+fn test_header_errors() {
+    assert_eq!(SkipList::<u32, u32>::from_snapshot(b"SKPX").err(), Some(SnapshotError::BadMagic));
+
+    let mut bytes = header(4, 0);
+    bytes[4] = 9;
+    assert_eq!(SkipList::<u32, u32>::from_snapshot(&bytes).err(), Some(SnapshotError::UnsupportedVersion(9)));
+
+    let mut bytes = header(4, 0);
+    bytes[5..13].copy_from_slice(&f64::NAN.to_le_bytes());
+    assert_eq!(SkipList::<u32, u32>::from_snapshot(&bytes).err(), Some(SnapshotError::InvalidProbability));
+
+    let bytes = header(0, 0);
+    assert_eq!(SkipList::<u32, u32>::from_snapshot(&bytes).err(), Some(SnapshotError::InvalidLevels(0)));
+    let bytes = header(65, 0);
+    assert_eq!(SkipList::<u32, u32>::from_snapshot(&bytes).err(), Some(SnapshotError::InvalidLevels(65)));
+
+    let bytes = header(4, 0);
+    assert!(SkipList::<u32, u32>::from_snapshot(&bytes).unwrap().is_empty());
+}
+
+#[test]
+fn test_huge_lengths_are_rejected_without_allocating() {
+    let bytes = header(4, u64::MAX);
+    assert_eq!(SkipList::<u32, u32>::from_snapshot(&bytes).err(), Some(SnapshotError::InvalidLength(u64::MAX)));
+
+    // A string claiming 4 GiB with only a few bytes behind it
+    let mut bytes = header(4, 1);
+    bytes.push(1);
+    bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+    bytes.extend_from_slice(b"abc");
+    assert_eq!(SkipList::<String, String>::from_snapshot(&bytes).err(), Some(SnapshotError::Truncated));
+}
+
+#[test]
+fn test_entry_errors() {
+    let mut bytes = header(2, 1);
+    entry(&mut bytes, 3, 1, 1);
+    assert_eq!(
+        SkipList::<u32, u32>::from_snapshot(&bytes).err(),
+        Some(SnapshotError::InvalidHeight { index: 0, height: 3 })
+    );
+
+    let mut bytes = header(2, 2);
+    entry(&mut bytes, 1, 5, 1);
+    entry(&mut bytes, 1, 5, 2);
+    assert_eq!(SkipList::<u32, u32>::from_snapshot(&bytes).err(), Some(SnapshotError::Unsorted { index: 1 }));
+
+    let mut bytes = header(2, 1);
+    entry(&mut bytes, 1, 5, 1);
+    bytes.push(0);
+    assert_eq!(SkipList::<u32, u32>::from_snapshot(&bytes).err(), Some(SnapshotError::TrailingBytes));
+
+    let mut bytes = header(2, 1);
+    bytes.push(1);
+    bytes.extend_from_slice(&2u32.to_le_bytes());
+    bytes.extend_from_slice(&[0xff, 0xfe]);
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    assert_eq!(SkipList::<String, u32>::from_snapshot(&bytes).err(), Some(SnapshotError::InvalidValue));
+}
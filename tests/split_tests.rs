@@ -0,0 +1,197 @@
+use skiplist_rs::SkipList;
+
+fn from_keys<I: IntoIterator<Item = i32>>(keys: I) -> SkipList<i32, String> {
+    let mut skiplist = SkipList::new();
+    for k in keys {
+        skiplist.insert(k, format!("value_{}", k));
+    }
+    skiplist
+}
+
+fn keys(skiplist: &SkipList<i32, String>) -> Vec<i32> {
+    skiplist.iter().map(|(k, _)| k).collect()
+}
+
+fn rev_keys(skiplist: &SkipList<i32, String>) -> Vec<i32> {
+    skiplist.iter().rev().map(|(k, _)| k).collect()
+}
+
+#[test]
+fn test_split_off_middle() {
+    let mut left = from_keys(0..100);
+
+    let right = left.split_off(&40);
+
+    assert_eq!(left.len(), 40);
+    assert_eq!(right.len(), 60);
+    assert_eq!(keys(&left), (0..40).collect::<Vec<_>>());
+    assert_eq!(keys(&right), (40..100).collect::<Vec<_>>());
+    assert_eq!(rev_keys(&left), (0..40).rev().collect::<Vec<_>>());
+    assert_eq!(rev_keys(&right), (40..100).rev().collect::<Vec<_>>());
+
+    // Lookups on each side only see their own keys
+    for i in 0..100 {
+        assert_eq!(left.contains_key(&i), i < 40);
+        assert_eq!(right.contains_key(&i), i >= 40);
+    }
+}
+
+#[test]
+fn test_split_off_between_keys() {
+    let mut left = from_keys((0..20).map(|i| i * 10));
+
+    let right = left.split_off(&55);
+
+    assert_eq!(keys(&left), vec![0, 10, 20, 30, 40, 50]);
+    assert_eq!(right.len(), 14);
+    assert_eq!(right.iter().next().map(|(k, _)| k), Some(60));
+}
+
+#[test]
+fn test_split_off_at_ends() {
+    let mut all = from_keys(0..10);
+
+    // Everything moves when splitting before the first key
+    let moved = all.split_off(&-1);
+    assert!(all.is_empty());
+    assert_eq!(moved.len(), 10);
+    assert_eq!(all.iter().next_back(), None);
+
+    // Nothing moves when splitting after the last key
+    let mut all = moved;
+    let nothing = all.split_off(&100);
+    assert!(nothing.is_empty());
+    assert_eq!(all.len(), 10);
+    assert_eq!(rev_keys(&all), (0..10).rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_split_halves_stay_usable() {
+    let mut left = from_keys(0..50);
+    let mut right = left.split_off(&25);
+
+    left.insert(100, "left_100".to_string());
+    right.insert(-1, "right_-1".to_string());
+    left.remove(&0);
+    right.remove(&49);
+
+    assert_eq!(left.len(), 25);
+    assert_eq!(right.len(), 25);
+    assert_eq!(keys(&left).last(), Some(&100));
+    assert_eq!(keys(&right).first(), Some(&-1));
+    assert_eq!(rev_keys(&right).first(), Some(&48));
+}
+
+#[test]
+fn test_append_disjoint() {
+    let mut low = from_keys(0..30);
+    let mut high = from_keys(30..80);
+
+    low.append(&mut high);
+
+    assert_eq!(low.len(), 80);
+    assert!(high.is_empty());
+    assert_eq!(keys(&low), (0..80).collect::<Vec<_>>());
+    assert_eq!(rev_keys(&low), (0..80).rev().collect::<Vec<_>>());
+    for i in 0..80 {
+        assert_eq!(low.get(&i), Some(format!("value_{}", i)));
+    }
+}
+
+#[test]
+fn test_append_disjoint_other_side() {
+    let mut high = from_keys(50..60);
+    let mut low = from_keys(0..10);
+
+    high.append(&mut low);
+
+    assert!(low.is_empty());
+    assert_eq!(keys(&high), (0..10).chain(50..60).collect::<Vec<_>>());
+    assert_eq!(rev_keys(&high).first(), Some(&59));
+}
+
+#[test]
+fn test_append_interleaved() {
+    let mut evens = from_keys((0..100).step_by(2));
+    let mut odds = from_keys((1..100).step_by(2));
+
+    evens.append(&mut odds);
+
+    assert_eq!(evens.len(), 100);
+    assert!(odds.is_empty());
+    assert_eq!(keys(&evens), (0..100).collect::<Vec<_>>());
+    assert_eq!(rev_keys(&evens), (0..100).rev().collect::<Vec<_>>());
+    for i in 0..100 {
+        assert!(evens.contains_key(&i));
+    }
+}
+
+#[test]
+fn test_append_overlapping_keys() {
+    let mut a = from_keys(0..20);
+    let mut b = SkipList::new();
+    for i in 10..30 {
+        b.insert(i, format!("other_{}", i));
+    }
+
+    a.append(&mut b);
+
+    assert_eq!(a.len(), 30);
+    assert_eq!(a.get(&5), Some("value_5".to_string()));
+    assert_eq!(a.get(&15), Some("other_15".to_string())); // Other wins on ties
+    assert_eq!(a.get(&25), Some("other_25".to_string()));
+}
+
+#[test]
+fn test_append_empty() {
+    let mut a = from_keys(0..5);
+    let mut empty = SkipList::new();
+
+    a.append(&mut empty);
+    assert_eq!(a.len(), 5);
+
+    empty.append(&mut a);
+    assert_eq!(empty.len(), 5);
+    assert!(a.is_empty());
+}
+
+#[test]
+fn test_split_then_append_round_trip() {
+    let mut list = from_keys(0..500);
+
+    let mut right = list.split_off(&123);
+    let mut tail = right.split_off(&400);
+    list.append(&mut tail);
+    list.append(&mut right);
+
+    assert_eq!(list.len(), 500);
+    assert_eq!(keys(&list), (0..500).collect::<Vec<_>>());
+    assert_eq!(rev_keys(&list), (0..500).rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_append_keeps_each_lists_level_probability() {
+    // Every tower is as tall as allowed with p = 1 and one level with p = 0
+    let flat = |keys: std::ops::Range<i32>| {
+        let mut list = SkipList::with_params(4, 0.0);
+        for k in keys {
+            list.insert(k, k);
+        }
+        list
+    };
+
+    // Appending into an empty list swaps the entries over
+    let mut tall = SkipList::with_params(4, 1.0);
+    tall.append(&mut flat(0..10));
+    tall.insert(100, 100);
+    assert_eq!(tall.stats().nodes_per_level[1], 1);
+
+    // So does appending a list whose keys all come first
+    let mut tall = SkipList::with_params(4, 1.0);
+    tall.insert(1000, 1000);
+    tall.append(&mut flat(0..10));
+    for k in 100..105 {
+        tall.insert(k, k);
+    }
+    assert_eq!(tall.stats().nodes_per_level[1], 6);
+}
@@ -0,0 +1,92 @@
+use skiplist_rs::SkipList;
+
+#[test]
+fn test_stats_empty() {
+    let skiplist: SkipList<i32, String> = SkipList::new();
+    let stats = skiplist.stats();
+
+    assert_eq!(stats.len, 0);
+    assert_eq!(stats.max_level, 4); // Initial levels from `new()`
+    assert_eq!(stats.nodes_per_level, vec![0; 4]);
+    assert_eq!(stats.max_height, 0);
+    assert_eq!(stats.avg_height, 0.0);
+    assert_eq!(stats.sampled_lookups, 0);
+    assert!(stats.node_bytes > 0); // The head is always allocated
+}
+
+#[test]
+fn test_stats_level_histogram() {
+    let mut skiplist = SkipList::new();
+    for i in 0..1000 {
+        skiplist.insert(i, i);
+    }
+    let stats = skiplist.stats();
+
+    assert_eq!(stats.len, 1000);
+    assert_eq!(stats.nodes_per_level.len(), stats.max_level);
+    assert_eq!(stats.nodes_per_level[0], 1000);
+
+    // Each level is a subset of the level below
+    for pair in stats.nodes_per_level.windows(2) {
+        assert!(pair[1] <= pair[0]);
+    }
+
+    // Roughly half the towers reach level 1 with p = 0.5
+    assert!(stats.nodes_per_level[1] > 300 && stats.nodes_per_level[1] < 700);
+    assert!(stats.max_height <= stats.max_level);
+    assert!(stats.avg_height > 1.5 && stats.avg_height < 2.5);
+}
+
+#[test]
+fn test_stats_levels_track_optimal() {
+    let mut skiplist = SkipList::new();
+    for i in 0..1000 {
+        skiplist.insert(i, i);
+    }
+    let stats = skiplist.stats();
+
+    // ceil(log2(1000)) + 2 = 12, matching the README table
+    assert_eq!(stats.optimal_levels, 12);
+    assert!(stats.max_level >= stats.optimal_levels);
+}
+
+#[test]
+fn test_stats_comparisons_are_logarithmic() {
+    let mut skiplist = SkipList::new();
+    for i in 0..10_000 {
+        skiplist.insert(i, i);
+    }
+    let stats = skiplist.stats();
+
+    assert_eq!(stats.sampled_lookups, 64);
+    assert!(stats.avg_comparisons >= 1.0);
+    // Far below a linear scan; the expected cost is a small multiple of log2(n)
+    assert!(stats.avg_comparisons < 100.0, "avg {}", stats.avg_comparisons);
+    assert!(stats.max_comparisons as f64 >= stats.avg_comparisons);
+}
+
+#[test]
+fn test_stats_memory_grows_with_entries() {
+    let mut skiplist = SkipList::new();
+    skiplist.insert(0, 0u64);
+    let small = skiplist.stats().node_bytes;
+
+    for i in 1..100 {
+        skiplist.insert(i, i as u64);
+    }
+    let large = skiplist.stats().node_bytes;
+
+    assert!(large > small * 10);
+}
+
+#[test]
+fn test_stats_display() {
+    let mut skiplist = SkipList::new();
+    for i in 0..10 {
+        skiplist.insert(i, i);
+    }
+    let text = skiplist.stats().to_string();
+
+    assert!(text.contains("Elements:        10"));
+    assert!(text.contains("Level  0:        10 nodes"));
+}
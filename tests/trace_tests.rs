@@ -0,0 +1,93 @@
+use skiplist_rs::{SearchObserver, SearchTrace, SkipList};
+
+fn numbered(n: i32) -> SkipList<i32, i32> {
+    let mut skiplist = SkipList::new();
+    for i in 0..n {
+        skiplist.insert(i, i * 10);
+    }
+    skiplist
+}
+
+#[test]
+fn test_get_traced_matches_get() {
+    let skiplist = numbered(500);
+
+    for key in [-1, 0, 123, 250, 499, 500] {
+        let (value, _) = skiplist.get_traced(&key);
+        assert_eq!(value, skiplist.get(&key));
+    }
+}
+
+#[test]
+fn test_trace_visits_levels_top_down() {
+    let skiplist = numbered(500);
+    let (_, trace) = skiplist.get_traced(&-1);
+
+    // A miss below every key walks every level without stepping forward
+    let levels: Vec<_> = trace.levels().iter().map(|l| l.level).collect();
+    let top = skiplist.stats().max_level;
+    assert_eq!(levels, (0..top).rev().collect::<Vec<_>>());
+    assert_eq!(trace.steps(), 0);
+}
+
+#[test]
+fn test_trace_counts_are_consistent() {
+    let skiplist = numbered(1000);
+
+    for key in (0..1000).step_by(37) {
+        let (_, trace) = skiplist.get_traced(&key);
+
+        assert!(trace.comparisons() >= 1);
+        for level in trace.levels() {
+            // Every step forward follows a comparison that said "keep going"
+            assert!(level.steps <= level.comparisons);
+            // Reading a link and reading the key behind it each take a lock
+            assert!(level.locks >= level.comparisons);
+        }
+        assert_eq!(trace.comparisons(), trace.levels().iter().map(|l| l.comparisons).sum());
+    }
+}
+
+#[test]
+fn test_trace_stops_at_match() {
+    let skiplist = numbered(100);
+    let (_, trace) = skiplist.get_traced(&50);
+
+    // The last visited level is where the key was found, which may be above 0
+    let last = trace.levels().last().unwrap();
+    assert!(last.comparisons >= 1);
+    assert!(trace.comparisons() < 100);
+}
+
+#[test]
+fn test_custom_observer() {
+    #[derive(Default)]
+    struct Descents(Vec<usize>);
+    impl SearchObserver for Descents {
+        fn on_descend(&mut self, level: usize) {
+            self.0.push(level);
+        }
+    }
+
+    let skiplist = numbered(50);
+    let mut observer = Descents::default();
+    assert_eq!(skiplist.get_observed(&1000, &mut observer), None);
+
+    let top = skiplist.stats().max_level;
+    assert_eq!(observer.0, (0..top).rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_insert_observed() {
+    let mut skiplist = numbered(200);
+
+    let mut trace = SearchTrace::default();
+    assert_eq!(skiplist.insert_observed(1000, 1, &mut trace), None);
+    assert!(trace.comparisons() >= 1);
+    // Inserting records a predecessor on every level, so every level is visited
+    assert_eq!(trace.levels().len(), skiplist.stats().max_level);
+
+    let mut trace = SearchTrace::default();
+    assert_eq!(skiplist.insert_observed(5, 0, &mut trace), Some(50));
+    assert_eq!(skiplist.get(&5), Some(0));
+}
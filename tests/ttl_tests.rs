@@ -0,0 +1,126 @@
+use skiplist_rs::{Clock, ManualClock, TtlSkipList};
+use std::time::Duration;
+
+fn secs(n: u64) -> Duration {
+    Duration::from_secs(n)
+}
+
+fn cache() -> (ManualClock, TtlSkipList<u32, String, ManualClock>) {
+    let clock = ManualClock::new();
+    (clock.clone(), TtlSkipList::with_clock(clock))
+}
+
+#[test]
+fn test_entries_expire_at_their_deadline() {
+    let (clock, mut cache) = cache();
+    cache.insert(1, "one".to_string(), secs(10));
+
+    clock.advance(secs(9));
+    assert_eq!(cache.get(&1), Some("one".to_string()));
+    assert_eq!(cache.time_to_live(&1), Some(secs(1)));
+
+    clock.advance(secs(1));
+    assert_eq!(cache.time_to_live(&1), None);
+    // Still stored until something touches it
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get(&1), None);
+    assert_eq!(cache.len(), 0);
+}
+
+#[test]
+fn test_expired_entries_are_hidden_from_iteration() {
+    let (clock, mut cache) = cache();
+    for i in 0..10 {
+        cache.insert(i, format!("v{}", i), secs(if i % 2 == 0 { 5 } else { 50 }));
+    }
+    cache.insert_permanent(100, "forever".to_string());
+
+    clock.advance(secs(5));
+    let keys: Vec<_> = cache.iter().map(|(k, _)| k).collect();
+    assert_eq!(keys, vec![1, 3, 5, 7, 9, 100]);
+    let rev: Vec<_> = cache.iter().rev().map(|(k, _)| k).collect();
+    assert_eq!(rev, vec![100, 9, 7, 5, 3, 1]);
+    // Iterating does not remove anything
+    assert_eq!(cache.len(), 11);
+}
+
+#[test]
+fn test_purge_removes_only_expired_entries() {
+    let (clock, mut cache) = cache();
+    for i in 0..100 {
+        cache.insert(i, i.to_string(), secs(u64::from(i % 10) + 1));
+    }
+    cache.insert_permanent(1000, "kept".to_string());
+
+    clock.advance(secs(3));
+    assert_eq!(cache.purge_expired(cache.clock().now()), 30);
+    assert_eq!(cache.len(), 71);
+    assert!(cache.iter().all(|(k, _)| k == 1000 || k % 10 >= 3));
+
+    // Nothing more has expired yet
+    assert_eq!(cache.purge_expired(cache.clock().now()), 0);
+
+    clock.advance(secs(100));
+    assert_eq!(cache.purge_expired(cache.clock().now()), 70);
+    assert_eq!(cache.iter().collect::<Vec<_>>(), vec![(1000, "kept".to_string())]);
+}
+
+#[test]
+fn test_reinsert_replaces_the_deadline() {
+    let (clock, mut cache) = cache();
+    cache.insert(1, "short".to_string(), secs(1));
+    assert_eq!(cache.insert(1, "long".to_string(), secs(100)), Some("short".to_string()));
+
+    clock.advance(secs(2));
+    assert_eq!(cache.purge_expired(cache.clock().now()), 0);
+    assert_eq!(cache.get(&1), Some("long".to_string()));
+
+    // Same deadline twice in a row must still leave one deadline behind
+    cache.insert(2, "a".to_string(), secs(5));
+    cache.insert(2, "b".to_string(), secs(5));
+    clock.advance(secs(5));
+    assert_eq!(cache.purge_expired(cache.clock().now()), 1);
+    assert!(!cache.contains_key(&2));
+
+    // Making an entry permanent drops its deadline
+    cache.insert(3, "c".to_string(), secs(1));
+    cache.insert_permanent(3, "c".to_string());
+    clock.advance(secs(10));
+    assert_eq!(cache.purge_expired(cache.clock().now()), 0);
+    assert_eq!(cache.get(&3), Some("c".to_string()));
+    assert_eq!(cache.time_to_live(&3), Some(Duration::MAX));
+}
+
+#[test]
+fn test_remove_and_insert_over_expired_entries() {
+    let (clock, mut cache) = cache();
+    cache.insert(1, "a".to_string(), secs(1));
+    cache.insert(2, "b".to_string(), secs(1));
+    clock.advance(secs(1));
+
+    assert_eq!(cache.remove(&1), None);
+    assert_eq!(cache.insert(2, "fresh".to_string(), secs(1)), None);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get(&2), Some("fresh".to_string()));
+    assert_eq!(cache.remove(&2), Some("fresh".to_string()));
+    assert!(cache.is_empty());
+    assert_eq!(cache.purge_expired(cache.clock().now()), 0);
+}
+
+#[test]
+fn test_huge_ttl_never_expires() {
+    let (clock, mut cache) = cache();
+    cache.insert(1, "x".to_string(), Duration::MAX);
+    clock.advance(secs(1_000_000));
+    assert_eq!(cache.purge_expired(cache.clock().now()), 0);
+    assert_eq!(cache.get(&1), Some("x".to_string()));
+}
+
+#[test]
+fn test_system_clock() {
+    let mut cache = TtlSkipList::new();
+    cache.insert("k", 1, Duration::ZERO);
+    cache.insert("j", 2, secs(3600));
+    assert_eq!(cache.get(&"k"), None);
+    assert_eq!(cache.get(&"j"), Some(2));
+}
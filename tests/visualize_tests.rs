@@ -0,0 +1,118 @@
+use skiplist_rs::{RenderOptions, SkipList};
+
+fn readme_list() -> SkipList<i32, ()> {
+    let mut skiplist = SkipList::new();
+    for k in [1, 4, 6, 9, 12, 15] {
+        skiplist.insert(k, ());
+    }
+    skiplist
+}
+
+#[test]
+fn test_ascii_level_zero() {
+    let skiplist = readme_list();
+    let drawing = skiplist.to_ascii();
+    let last = drawing.lines().last().unwrap();
+
+    assert!(last.ends_with("[1] -> [4] -> [6] -> [9] -> [12] -> [15]"));
+    assert!(last.starts_with("Level 0:"));
+}
+
+#[test]
+fn test_ascii_lanes_align_with_level_zero() {
+    let mut skiplist = SkipList::new();
+    for k in 0..40 {
+        skiplist.insert(k * 3, ());
+    }
+    let drawing = skiplist.to_ascii();
+    let lines: Vec<_> = drawing.lines().collect();
+    let bottom = lines.last().unwrap();
+
+    // One line per level up to the tallest tower
+    assert_eq!(lines.len(), skiplist.stats().max_height);
+
+    for line in &lines {
+        // Every cell drawn on an upper lane sits right above the same cell on level 0
+        for (col, _) in line.match_indices('[') {
+            let cell_end = col + line[col..].find(']').unwrap() + 1;
+            assert_eq!(&line[col..cell_end], &bottom[col..cell_end]);
+        }
+        // Lanes only contain cells and arrows
+        let lane = line.split_once(':').unwrap().1;
+        assert!(lane.chars().all(|c| "[]-> 0123456789".contains(c)));
+    }
+}
+
+#[test]
+fn test_ascii_empty() {
+    let skiplist: SkipList<i32, ()> = SkipList::new();
+    assert_eq!(skiplist.to_ascii(), "(empty)\n");
+}
+
+#[test]
+fn test_ascii_truncated() {
+    let mut skiplist = SkipList::new();
+    for k in 0..1000 {
+        skiplist.insert(k, ());
+    }
+    let drawing = skiplist.to_ascii_with(&RenderOptions::with_max_nodes(5));
+    let lines: Vec<_> = drawing.lines().collect();
+
+    assert_eq!(lines.last(), Some(&"(995 more entries)"));
+    assert!(lines[lines.len() - 2].ends_with("[0] -> [1] -> [2] -> [3] -> [4] -> ..."));
+
+    // Every "..." lines up with the one on level 0
+    let col = lines[lines.len() - 2].find("...").unwrap();
+    for line in &lines[..lines.len() - 1] {
+        if let Some(found) = line.find("...") {
+            assert_eq!(found, col);
+        }
+    }
+}
+
+#[test]
+fn test_dot_structure() {
+    let skiplist = readme_list();
+    let dot = skiplist.to_dot();
+
+    assert!(dot.starts_with("digraph skiplist {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert!(dot.contains("head [label="));
+    for i in 0..6 {
+        assert!(dot.contains(&format!("    n{} [label=", i)));
+    }
+
+    // Level 0 is a chain through every node
+    assert!(dot.contains("head:l0 -> n0:l0;"));
+    for i in 0..5 {
+        assert!(dot.contains(&format!("n{}:l0 -> n{}:l0;", i, i + 1)));
+    }
+
+    // One edge per forward pointer: level 0 has 6, upper levels match the histogram
+    let stats = skiplist.stats();
+    let edges = dot.matches(" -> ").count();
+    assert_eq!(edges, stats.nodes_per_level.iter().sum::<usize>());
+    assert!(!dot.contains("more"));
+}
+
+#[test]
+fn test_dot_truncated() {
+    let mut skiplist = SkipList::new();
+    for k in 0..100 {
+        skiplist.insert(k, ());
+    }
+    let dot = skiplist.to_dot_with(&RenderOptions::with_max_nodes(2));
+
+    assert!(dot.contains("more [shape=plaintext, label=\"... 98 more\"];"));
+    assert!(dot.contains("n1:l0 -> more;"));
+    assert!(!dot.contains("n2"));
+}
+
+#[test]
+fn test_dot_escapes_labels() {
+    let mut skiplist = SkipList::new();
+    skiplist.insert("a|b".to_string(), ());
+    let dot = skiplist.to_dot();
+
+    assert!(dot.contains("a\\|b"));
+}